@@ -10,9 +10,22 @@ git-description = Git commit {$hash} on {$date}
 
 ## Color Spaces
 rgb = RGB
+srgb-linear = Linear sRGB
+display-p3 = Display P3
+a98-rgb = Adobe RGB (1998)
+prophoto-rgb = ProPhoto RGB
+rec2020 = Rec. 2020
 hsv = HSV
+hsl = HSL
+hwb = HWB
+lab-d50 = CIELAB (D50)
+lab-d65 = CIELAB (D65)
+lch-d50 = CIELCh (D50)
+lch-d65 = CIELCh (D65)
 oklab = OKLAB
 oklch = OKLCH
+xyz = CIE XYZ (D65)
+xyy = CIE xyY
 cmyk = CMYK
 
 ## Colors
@@ -32,3 +45,139 @@ saturation = Saturation
 lightness = Lightness
 value = Value
 chroma = Chroma
+whiteness = Whiteness
+blackness = Blackness
+tristimulus-x = X
+tristimulus-y = Y
+tristimulus-z = Z
+chromaticity-x = x
+chromaticity-y = y
+luminance = Luminance
+alpha = Alpha
+
+## Hex
+hex = Hex
+invalid-hex = Expected #RGB, #RGBA, #RRGGBB or #RRGGBBAA
+
+## Copy Formats
+copy-formats = Copy Formats
+copy-formats-help = Placeholders use the component names of a column's color space, like {$examples}. Append 8 for 0–255 and % for percentages, and :.N for N decimals. Prefix a placeholder with a color space, like {$qualified}, to convert to it first.
+copy-format-template = Template
+add-copy-format = Add Format
+
+## Settings
+settings = Settings
+default-space = Default color space
+precision = Copy precision
+out-of-gamut = Copying out of gamut colors
+out-of-gamut-copy = Copy as is
+out-of-gamut-clamp = Clamp into gamut
+out-of-gamut-refuse = Don't copy
+snap-sliders = Snap sliders
+snap-sliders-description = Hues snap to whole degrees and RGB to 8-bit values. Hold Shift for fine steps and precise drags, Ctrl for coarse steps.
+
+## Layouts
+sliders = Sliders
+wheel-triangle = Hue wheel and triangle
+wheel-square = Hue wheel and square
+oklch-disc = OKLCh disc
+
+## Gamut
+gamut-srgb = Inside sRGB
+gamut-display-p3 = Outside sRGB, inside Display P3
+gamut-rec2020 = Outside Display P3, inside Rec. 2020
+gamut-out = Outside Rec. 2020
+copy-clamped = Copied with out of gamut values clamped
+copy-refused = Not copied, the color is out of gamut
+
+## Contrast
+contrast = Contrast
+column = Column {$number}
+foreground = Foreground
+background = Background
+contrast-ratio = Contrast ratio
+foreground-luminance = Foreground luminance
+background-luminance = Background luminance
+aa-normal = AA normal text
+aaa-normal = AAA normal text
+aa-large = AA large text
+aaa-large = AAA large text
+pass = Pass
+fail = Fail
+contrast-sample = The quick brown fox jumps over the lazy dog
+apca = APCA
+lightness-contrast = Lightness contrast
+polarity = Polarity
+dark-on-light = Dark text on a light background
+light-on-dark = Light text on a dark background
+font-weight = Minimum size at weight {$weight}
+font-size = {$size}px
+non-text-only = Non-text only
+unusable = Unusable
+
+## Difference
+difference = Color Difference
+difference-metric = Metric
+difference-threshold = Identical below
+difference-identical = Pairs marked with a check are perceptually identical.
+
+## Color vision
+color-vision = Color Vision
+deficiency = Deficiency
+normal-vision = Normal vision
+protan = Protan (red)
+deutan = Deutan (green)
+tritan = Tritan (blue)
+severity = Severity
+protanomaly = Protanomaly
+protanopia = Protanopia
+deuteranomaly = Deuteranomaly
+deuteranopia = Deuteranopia
+tritanomaly = Tritanomaly
+tritanopia = Tritanopia
+simulate-view = Simulate every column
+simulate-view-description = Sliders and graphs are shown as seen with the deficiency.
+simulated = Simulated
+
+## Harmony
+harmony = Harmony
+harmony-rule = Rule
+harmony-spread = Angle spread
+harmony-source = Generated from column { $column }
+complementary = Complementary
+analogous = Analogous
+triadic = Triadic
+split-complementary = Split complementary
+tetradic = Tetradic
+square = Square
+add-harmony = Add { $count } columns
+
+## Scale
+scale = Tint and Shade Scale
+scale-source = Base color
+scale-steps = Steps
+scale-space = Lightness in
+easing = Easing
+linear = Linear
+ease-in = Ease in
+ease-out = Ease out
+ease-in-out = Ease in and out
+vary-chroma = Reduce chroma toward the ends
+palette-name = Palette name
+palette-format = Format
+append-columns = Append as columns
+export-palette = Copy palette
+
+## Gradient
+gradient = Gradient
+gradient-first = First stop
+gradient-last = Last stop
+interpolation = Interpolate in
+hue-interpolation = Hue
+shorter-hue = Shorter
+longer-hue = Longer
+gradient-needs-stops = Add another column to use as a stop.
+gradient-max-stops = Only the first { $count } columns are used as stops.
+samples = Samples
+copy-samples = Copy samples
+copy-css = Copy CSS
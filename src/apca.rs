@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! APCA-W3 0.0.98G-4g lightness contrast (Lc), the contrast method of the
+//! WCAG 3 draft.
+//! https://github.com/Myndex/apca-w3
+
+use crate::contrast::composite;
+
+const MAIN_TRC: f32 = 2.4;
+const RED: f32 = 0.2126729;
+const GREEN: f32 = 0.7151522;
+const BLUE: f32 = 0.0721750;
+
+const NORM_BG: f32 = 0.56;
+const NORM_TXT: f32 = 0.57;
+const REV_TXT: f32 = 0.62;
+const REV_BG: f32 = 0.65;
+
+const BLACK_THRESHOLD: f32 = 0.022;
+const BLACK_CLAMP: f32 = 1.414;
+const SCALE: f32 = 1.14;
+const LOW_OFFSET: f32 = 0.027;
+const LOW_CLIP: f32 = 0.1;
+const DELTA_Y_MIN: f32 = 0.0005;
+
+/// Which of the two colors is lighter, APCA isn't symmetric
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    /// Dark text on a light background, positive Lc
+    DarkOnLight,
+    /// Light text on a dark background, negative Lc
+    LightOnDark,
+}
+
+impl Polarity {
+    pub fn of(lc: f32) -> Self {
+        if lc < 0.0 {
+            Polarity::LightOnDark
+        } else {
+            Polarity::DarkOnLight
+        }
+    }
+}
+
+/// Screen luminance estimate of sRGB, clamped into gamut
+fn screen_luminance(rgb: [f32; 3]) -> f32 {
+    let [r, g, b] = rgb.map(|c| c.clamp(0.0, 1.0).powf(MAIN_TRC));
+    let y = RED * r + GREEN * g + BLUE * b;
+
+    // Soft clamp of near black
+    if y > BLACK_THRESHOLD {
+        y
+    } else {
+        y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+    }
+}
+
+/// The lightness contrast of `text` over `background`, from about -108 to
+/// 106. The background's alpha is ignored.
+pub fn lightness_contrast(text: [f32; 4], background: [f32; 4]) -> f32 {
+    let background = [background[0], background[1], background[2]];
+    let text = screen_luminance(composite(text, background));
+    let background = screen_luminance(background);
+
+    if (background - text).abs() < DELTA_Y_MIN {
+        return 0.0;
+    }
+
+    let output = if background > text {
+        let sapc = (background.powf(NORM_BG) - text.powf(NORM_TXT)) * SCALE;
+        if sapc < LOW_CLIP {
+            0.0
+        } else {
+            sapc - LOW_OFFSET
+        }
+    } else {
+        let sapc = (background.powf(REV_BG) - text.powf(REV_TXT)) * SCALE;
+        if sapc > -LOW_CLIP {
+            0.0
+        } else {
+            sapc + LOW_OFFSET
+        }
+    };
+
+    output * 100.0
+}
+
+/// The minimum font size of a weight at some Lc
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MinimumSize {
+    /// In CSS pixels
+    Px(f32),
+    /// Only for non-text elements, like dividers or disabled controls
+    NonText,
+    /// Too little contrast for anything
+    Unusable,
+}
+
+/// The font weights of [`FONT_SIZES`] rows
+pub const FONT_WEIGHTS: [u16; 9] = [100, 200, 300, 400, 500, 600, 700, 800, 900];
+
+const NON_TEXT: f32 = 777.0;
+const UNUSABLE: f32 = 999.0;
+
+/// The APCA font lookup table, minimum sizes in px of each of
+/// [`FONT_WEIGHTS`] by Lc in steps of 5
+#[rustfmt::skip]
+const FONT_SIZES: [[f32; 9]; 21] = [
+    [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0], // 0
+    [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0], // 5
+    [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0], // 10
+    [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0], // 15
+    [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0], // 20
+    [777.0, 777.0, 777.0, 120.0, 120.0, 108.0, 96.0, 96.0, 96.0],    // 25
+    [777.0, 777.0, 120.0, 108.0, 108.0, 96.0, 72.0, 72.0, 72.0],     // 30
+    [777.0, 120.0, 108.0, 96.0, 72.0, 60.0, 48.0, 48.0, 48.0],       // 35
+    [120.0, 108.0, 96.0, 60.0, 48.0, 42.0, 32.0, 32.0, 32.0],        // 40
+    [108.0, 96.0, 72.0, 42.0, 32.0, 28.0, 24.0, 24.0, 24.0],         // 45
+    [96.0, 72.0, 60.0, 32.0, 28.0, 24.0, 21.0, 21.0, 21.0],          // 50
+    [80.0, 60.0, 48.0, 28.0, 24.0, 21.0, 18.0, 18.0, 18.0],          // 55
+    [72.0, 48.0, 42.0, 24.0, 21.0, 18.0, 16.0, 16.0, 18.0],          // 60
+    [68.0, 46.0, 32.0, 21.75, 19.0, 17.0, 15.0, 16.0, 18.0],         // 65
+    [64.0, 44.0, 28.0, 19.5, 18.0, 16.0, 14.5, 16.0, 18.0],          // 70
+    [60.0, 42.0, 24.0, 18.0, 16.0, 15.0, 14.0, 16.0, 18.0],          // 75
+    [56.0, 38.25, 23.0, 17.25, 15.81, 14.0, 13.0, 16.0, 18.0],       // 80
+    [52.0, 34.5, 22.0, 16.5, 15.625, 13.5, 13.0, 16.0, 18.0],        // 85
+    [48.0, 32.0, 21.0, 16.0, 15.5, 13.0, 12.0, 16.0, 18.0],          // 90
+    [45.0, 28.0, 19.5, 15.5, 15.0, 12.0, 11.0, 16.0, 18.0],          // 95
+    [42.0, 26.5, 18.5, 15.0, 14.5, 11.0, 10.0, 16.0, 18.0],          // 100
+];
+
+/// The minimum font size of every one of [`FONT_WEIGHTS`] at `lc`, of
+/// either polarity. Lc is rounded down to the table's steps.
+pub fn minimum_sizes(lc: f32) -> [MinimumSize; 9] {
+    let row = ((lc.abs() / 5.0).floor() as usize).min(FONT_SIZES.len() - 1);
+    FONT_SIZES[row].map(|size| {
+        if size == UNUSABLE {
+            MinimumSize::Unusable
+        } else if size == NON_TEXT {
+            MinimumSize::NonText
+        } else {
+            MinimumSize::Px(size)
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lightness_contrast, minimum_sizes, MinimumSize, Polarity};
+
+    fn hex(hex: u32) -> [f32; 4] {
+        let c = |shift: u32| ((hex >> shift) & 0xFF) as f32 / 255.0;
+        [c(16), c(8), c(0), 1.0]
+    }
+
+    // https://github.com/Myndex/apca-w3#reference-values
+    #[test]
+    #[allow(clippy::excessive_precision)]
+    fn reference_values() {
+        let lc = |text, background| lightness_contrast(hex(text), hex(background));
+        assert!(aprox_eq(lc(0x888888, 0xFFFFFF), 63.056469930209424));
+        assert!(aprox_eq(lc(0xFFFFFF, 0x888888), -68.54146436644962));
+        assert!(aprox_eq(lc(0x000000, 0xAAAAAA), 58.146262578561334));
+        assert!(aprox_eq(lc(0xAAAAAA, 0x000000), -56.24113336839742));
+        assert!(aprox_eq(lc(0x112233, 0xDDEEFF), 91.66830811481631));
+        assert!(aprox_eq(lc(0xDDEEFF, 0x112233), -93.06770049484275));
+        assert!(aprox_eq(lc(0x112233, 0x444444), 8.32326136957393));
+        assert!(aprox_eq(lc(0x444444, 0x112233), -7.526878090491204));
+    }
+
+    #[test]
+    fn polarity() {
+        assert_eq!(
+            Polarity::of(lightness_contrast(hex(0), hex(0xFFFFFF))),
+            Polarity::DarkOnLight
+        );
+        assert_eq!(
+            Polarity::of(lightness_contrast(hex(0xFFFFFF), hex(0))),
+            Polarity::LightOnDark
+        );
+        assert_eq!(lightness_contrast(hex(0x777777), hex(0x777777)), 0.0);
+    }
+
+    #[test]
+    fn font_sizes() {
+        assert_eq!(minimum_sizes(5.0)[3], MinimumSize::Unusable);
+        assert_eq!(minimum_sizes(-17.0)[3], MinimumSize::NonText);
+        assert_eq!(minimum_sizes(63.0)[3], MinimumSize::Px(24.0));
+        assert_eq!(minimum_sizes(-78.0)[6], MinimumSize::Px(14.0));
+        assert_eq!(minimum_sizes(108.0)[3], MinimumSize::Px(15.0));
+    }
+
+    fn aprox_eq(a: f32, b: f64) -> bool {
+        const EPSILON: f32 = 1e-2;
+
+        a > b as f32 - EPSILON && a < b as f32 + EPSILON
+    }
+}
@@ -1,15 +1,33 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
-use crate::colorspace::{ColorSpace, ColorSpaceCombo, ColorSpaceMessage};
+use crate::apca::{self, MinimumSize, Polarity};
+use crate::colorspace::{
+    self,
+    cie::RgbSpace,
+    gamut::{self, Gamut},
+    ColorModel, ColorSpace, ColorSpaceMessage, PickerLayout,
+};
+use crate::config::{self, Config, OutOfGamutCopy, SavedSpace};
+use crate::contrast;
+use crate::css;
+use crate::cvd::{Deficiency, Simulation};
+use crate::delta_e::DeltaE;
 use crate::fl;
-use crate::widgets::color_block;
+use crate::format;
+use crate::gradient::{self, HueMethod, Interpolation};
+use crate::harmony::{self, Harmony};
+use crate::hex;
+use crate::scale::{self, Easing, PaletteFormat, Scale, ScaleSpace};
+use crate::shaders;
+use crate::widgets::{color_block, renderer_probe};
 use cosmic::app::context_drawer::ContextDrawer;
 use cosmic::app::{Core, Task};
+use cosmic::cosmic_config;
 use cosmic::iced::alignment::{Horizontal, Vertical};
 use cosmic::iced::keyboard::{Key, Modifiers};
-use cosmic::iced::{clipboard, Length};
+use cosmic::iced::{clipboard, time, Alignment, Length};
 use cosmic::iced::{event, keyboard::Event as KeyEvent, Color, Event, Subscription};
 use cosmic::iced_widget::scrollable::{Direction, Scrollbar};
 use cosmic::widget::menu::{self, action::MenuAction, MenuBar};
@@ -18,15 +36,96 @@ use log::info;
 
 pub struct ColorPicker {
     pub spaces: Vec<ColorSpace>,
+    /// The columns differ from the saved ones. Slider steps and typing are
+    /// saved every [`SPACES_SAVE_INTERVAL`], other changes right away.
+    spaces_changed: bool,
+    /// The columns saved by this instance that the config watcher hasn't
+    /// echoed yet, oldest first
+    unechoed_spaces: Vec<Vec<SavedSpace>>,
     last_edited: usize,
     show_graphs: bool,
+    snap_sliders: bool,
+    /// The hex field currently being typed into, kept verbatim until its
+    /// column changes some other way.
+    hex_edit: Option<(usize, String)>,
+    /// A notice about the last copy of a column, kept until it changes
+    copy_notice: Option<(usize, String)>,
+    /// The selected copy format of each model id, indexing its built-in
+    /// formats followed by `custom_formats`.
+    copy_formats: HashMap<String, usize>,
+    custom_formats: Vec<String>,
+    default_space: &'static dyn ColorModel,
+    precision: usize,
+    out_of_gamut_copy: OutOfGamutCopy,
+    /// The columns compared by the contrast page
+    contrast_foreground: usize,
+    contrast_background: usize,
+    delta_e: DeltaE,
+    delta_e_threshold: f32,
+    /// The threshold field, kept verbatim while it doesn't parse
+    delta_e_threshold_string: String,
+    /// The simulated color vision deficiency, if any
+    deficiency: Option<Deficiency>,
+    deficiency_severity: f32,
+    /// Simulate the deficiency in every column instead of only the swatches
+    simulate_view: bool,
+    /// The column the harmony page generates from
+    harmony_source: usize,
+    harmony: Harmony,
+    harmony_spread: f32,
+    /// The column the scale page generates from
+    scale_source: usize,
+    scale: Scale,
+    /// The palette name used by the export
+    scale_name: String,
+    scale_export: PaletteFormat,
+    /// The first and last column used as gradient stops
+    gradient_first: usize,
+    gradient_last: usize,
+    gradient_interpolation: Interpolation,
+    gradient_hue: HueMethod,
+    gradient_samples: usize,
+    context_page: ContextPage,
+    config_handler: Option<cosmic_config::Config>,
+    /// The last saved or loaded config
+    config: Config,
 
-    colorspace_selections: Vec<ColorSpaceCombo>,
+    colorspace_selections: Vec<&'static dyn ColorModel>,
     colorspace_names: Vec<String>,
+    copy_format_names: HashMap<&'static str, Vec<String>>,
+    out_of_gamut_copy_names: Vec<String>,
+    deficiency_names: Vec<String>,
+    harmony_names: Vec<String>,
+    scale_space_names: Vec<String>,
+    easing_names: Vec<String>,
+    interpolation_names: Vec<String>,
+    hue_method_names: Vec<String>,
+    layout_names: Vec<String>,
+    column_names: Vec<String>,
     keybinds: HashMap<menu::KeyBind, Action>,
     core: Core,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ContextPage {
+    #[default]
+    About,
+    CopyFormats,
+    Settings,
+    Contrast,
+    Difference,
+    ColorVision,
+    Harmony,
+    Scale,
+    Gradient,
+}
+
+#[derive(Clone, Debug)]
+pub struct Flags {
+    pub config_handler: Option<cosmic_config::Config>,
+    pub config: Config,
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     None,
@@ -36,16 +135,69 @@ pub enum Message {
     },
     ChangeColorSpace {
         index: usize,
-        selected: ColorSpaceCombo,
+        selected: &'static dyn ColorModel,
+    },
+    ChangeLayout {
+        index: usize,
+        layout: usize,
+    },
+    ChangeHex {
+        index: usize,
+        string: String,
     },
     AddSpace,
     RemoveSpace(usize),
+    MapIntoGamut(usize),
+    OpenHarmony(usize),
+    ChangeHarmony(usize),
+    ChangeHarmonySpread(f32),
+    AddHarmony,
+    ChangeScaleSource(usize),
+    ChangeScaleSteps(usize),
+    ChangeScaleSpace(usize),
+    ChangeScaleEasing(usize),
+    ToggleScaleChroma(bool),
+    ChangeScaleName(String),
+    ChangeScaleExport(usize),
+    AppendScale,
+    ExportScale,
+    ChangeGradientFirst(usize),
+    ChangeGradientLast(usize),
+    ChangeGradientInterpolation(usize),
+    ChangeGradientHue(usize),
+    ChangeGradientSamples(usize),
+    CopyGradientCss,
+    CopyGradientSamples,
 
     ToggleGraphs,
-    ToggleAboutPage,
+    ToggleContextPage(ContextPage),
     LaunchUrl(String),
 
+    SelectCopyFormat {
+        space: &'static str,
+        format: usize,
+    },
+    AddCopyFormat,
+    ChangeCopyFormat {
+        index: usize,
+        template: String,
+    },
+    RemoveCopyFormat(usize),
+    ChangeDefaultSpace(usize),
+    ChangePrecision(usize),
+    ChangeOutOfGamutCopy(usize),
+    ToggleSnapSliders(bool),
+    ChangeContrastForeground(usize),
+    ChangeContrastBackground(usize),
+    ChangeDeltaE(usize),
+    ChangeDeltaEThreshold(String),
+    ChangeDeficiency(usize),
+    ChangeDeficiencySeverity(f32),
+    ToggleSimulateView(bool),
+    UpdateConfig(Config),
+    SaveSpaces,
     CopyToClipboard(usize),
+    Paste(Option<String>),
     PickScreenRequest(usize),
     PickScreenResponse((usize, ashpd::desktop::Color)),
     Key(Key, Modifiers),
@@ -54,6 +206,13 @@ pub enum Message {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Action {
     ToggleGraphs,
+    CopyFormats,
+    Settings,
+    Contrast,
+    Difference,
+    ColorVision,
+    Scale,
+    Gradient,
     About,
 }
 
@@ -63,15 +222,43 @@ impl MenuAction for Action {
     fn message(&self) -> Message {
         match self {
             Action::ToggleGraphs => Message::ToggleGraphs,
-            Action::About => Message::ToggleAboutPage,
+            Action::CopyFormats => Message::ToggleContextPage(ContextPage::CopyFormats),
+            Action::Settings => Message::ToggleContextPage(ContextPage::Settings),
+            Action::Contrast => Message::ToggleContextPage(ContextPage::Contrast),
+            Action::Difference => Message::ToggleContextPage(ContextPage::Difference),
+            Action::ColorVision => Message::ToggleContextPage(ContextPage::ColorVision),
+            Action::Scale => Message::ToggleContextPage(ContextPage::Scale),
+            Action::Gradient => Message::ToggleContextPage(ContextPage::Gradient),
+            Action::About => Message::ToggleContextPage(ContextPage::About),
         }
     }
 }
 
+/// How often columns changed by sliders and typing are saved
+const SPACES_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+/// The most saves of the columns waiting for their echo, in case the
+/// watcher drops some
+const MAX_UNECHOED_SPACES: usize = 8;
+
+/// The copy precisions, `0..=config::MAX_PRECISION`
+const PRECISIONS: [&str; config::MAX_PRECISION + 1] = ["0", "1", "2", "3", "4", "5", "6"];
+
+/// The step counts of the scale and gradient pages, `MIN_STEPS..`
+const STEP_COUNTS: [&str; 13] = [
+    "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15",
+];
+const MIN_STEPS: usize = 3;
+
+/// The names of `PaletteFormat::ALL`
+const PALETTE_FORMAT_NAMES: [&str; 3] = ["CSS", "JSON", "GIMP"];
+
+/// The names of `DeltaE::ALL`
+const DELTA_E_NAMES: [&str; 4] = ["ΔE*76", "ΔE*94", "CIEDE2000", "ΔEOK"];
+
 impl Application for ColorPicker {
     type Executor = cosmic::executor::Default;
 
-    type Flags = ();
+    type Flags = Flags;
 
     type Message = Message;
 
@@ -97,6 +284,13 @@ impl Application for ColorPicker {
                         self.show_graphs,
                         Action::ToggleGraphs,
                     ),
+                    menu::Item::Button(fl!("copy-formats"), None, Action::CopyFormats),
+                    menu::Item::Button(fl!("settings"), None, Action::Settings),
+                    menu::Item::Button(fl!("contrast"), None, Action::Contrast),
+                    menu::Item::Button(fl!("difference"), None, Action::Difference),
+                    menu::Item::Button(fl!("color-vision"), None, Action::ColorVision),
+                    menu::Item::Button(fl!("scale"), None, Action::Scale),
+                    menu::Item::Button(fl!("gradient"), None, Action::Gradient),
                     menu::Item::Button(fl!("menu-about"), None, Action::About),
                 ],
             ),
@@ -108,7 +302,7 @@ impl Application for ColorPicker {
         vec![widget::text::heading(fl!("app-title")).into()]
     }
 
-    fn init(core: Core, _flags: Self::Flags) -> (Self, Task<Self::Message>) {
+    fn init(core: Core, flags: Self::Flags) -> (Self, Task<Self::Message>) {
         let mut keybinds = HashMap::new();
         keybinds.insert(
             menu::KeyBind {
@@ -119,18 +313,92 @@ impl Application for ColorPicker {
         );
 
         let mut app = ColorPicker {
-            spaces: vec![ColorSpace::default()],
+            spaces: vec![],
+            spaces_changed: false,
+            unechoed_spaces: vec![],
             last_edited: 0,
             show_graphs: false,
+            snap_sliders: false,
+            hex_edit: None,
+            copy_notice: None,
+            copy_formats: HashMap::new(),
+            custom_formats: vec![],
+            default_space: ColorSpace::default().model(),
+            precision: format::DEFAULT_PRECISION,
+            out_of_gamut_copy: OutOfGamutCopy::default(),
+            contrast_foreground: 0,
+            contrast_background: 1,
+            delta_e: DeltaE::default(),
+            delta_e_threshold: DeltaE::default().just_noticeable(),
+            delta_e_threshold_string: DeltaE::default().just_noticeable().to_string(),
+            deficiency: None,
+            deficiency_severity: 1.0,
+            simulate_view: false,
+            harmony_source: 0,
+            harmony: Harmony::default(),
+            harmony_spread: 0.0,
+            scale_source: 0,
+            scale: Scale {
+                steps: 11,
+                space: ScaleSpace::default(),
+                easing: Easing::default(),
+                vary_chroma: false,
+            },
+            scale_name: String::from("color"),
+            scale_export: PaletteFormat::default(),
+            gradient_first: 0,
+            gradient_last: 1,
+            gradient_interpolation: Interpolation::default(),
+            gradient_hue: HueMethod::default(),
+            gradient_samples: 5,
+            context_page: ContextPage::default(),
+            config_handler: flags.config_handler,
+            config: Config::default(),
 
-            colorspace_selections: vec![
-                ColorSpaceCombo::Rgb,
-                ColorSpaceCombo::Hsv,
-                ColorSpaceCombo::Oklab,
-                ColorSpaceCombo::Oklch,
-                ColorSpaceCombo::Cmyk,
-            ],
+            colorspace_selections: colorspace::MODELS.to_vec(),
             colorspace_names: vec![],
+            copy_format_names: HashMap::new(),
+            out_of_gamut_copy_names: vec![
+                fl!("out-of-gamut-copy"),
+                fl!("out-of-gamut-clamp"),
+                fl!("out-of-gamut-refuse"),
+            ],
+            deficiency_names: vec![
+                fl!("normal-vision"),
+                fl!("protan"),
+                fl!("deutan"),
+                fl!("tritan"),
+            ],
+            harmony_names: vec![
+                fl!("complementary"),
+                fl!("analogous"),
+                fl!("triadic"),
+                fl!("split-complementary"),
+                fl!("tetradic"),
+                fl!("square"),
+            ],
+            scale_space_names: vec![fl!("oklch"), fl!("lch-d50")],
+            easing_names: vec![
+                fl!("linear"),
+                fl!("ease-in"),
+                fl!("ease-out"),
+                fl!("ease-in-out"),
+            ],
+            interpolation_names: vec![
+                fl!("rgb"),
+                fl!("srgb-linear"),
+                fl!("oklab"),
+                fl!("oklch"),
+                fl!("hsv"),
+            ],
+            hue_method_names: vec![fl!("shorter-hue"), fl!("longer-hue")],
+            layout_names: vec![
+                fl!("sliders"),
+                fl!("wheel-triangle"),
+                fl!("wheel-square"),
+                fl!("oklch-disc"),
+            ],
+            column_names: vec![],
             keybinds,
             core,
         };
@@ -138,8 +406,10 @@ impl Application for ColorPicker {
         app.colorspace_names = app
             .colorspace_selections
             .iter()
-            .map(|cs| cs.to_string())
+            .map(|model| model.name())
             .collect();
+        app.apply_config(flags.config);
+        app.load_spaces();
 
         let command = app.set_window_title(fl!("app-title"));
         (app, command)
@@ -148,35 +418,219 @@ impl Application for ColorPicker {
     fn update(&mut self, message: Self::Message) -> Task<Self::Message> {
         match message {
             Message::None => (),
-            Message::ColorSpace { index: i, message } => match message {
-                ColorSpaceMessage::ChangeValue { index, value } => {
-                    self.spaces[i].change_value(index, value);
-                }
-                ColorSpaceMessage::ChangeString { index, string } => {
-                    self.spaces[i].change_string(index, string);
+            Message::ColorSpace { index: i, message } => {
+                self.last_edited = i;
+                self.column_changed(i);
+                match message {
+                    ColorSpaceMessage::ChangeValue { index, value } => {
+                        self.spaces[i].change_value(index, value);
+                    }
+                    ColorSpaceMessage::ChangeValues { values } => {
+                        for (index, value) in values {
+                            self.spaces[i].change_value(index, value);
+                        }
+                    }
+                    ColorSpaceMessage::ChangeString { index, string } => {
+                        self.spaces[i].change_string(index, string);
+                    }
+                    ColorSpaceMessage::ChangeRgb([r, g, b]) => {
+                        let alpha = self.spaces[i].get_rgb()[3];
+                        self.spaces[i].from_rgb([r, g, b, alpha]);
+                    }
+                    ColorSpaceMessage::ChangeOklch(lch) => {
+                        self.spaces[i].set_oklch(lch);
+                    }
                 }
-            },
+            }
             Message::ChangeColorSpace { index, selected } => {
-                self.spaces[index] = match selected {
-                    ColorSpaceCombo::Rgb => self.spaces[index].to_rgb(),
-                    ColorSpaceCombo::Hsv => self.spaces[index].to_hsv(),
-                    ColorSpaceCombo::Oklab => self.spaces[index].to_oklab(),
-                    ColorSpaceCombo::Oklch => self.spaces[index].to_oklch(),
-                    ColorSpaceCombo::Cmyk => self.spaces[index].to_cmyk(),
-                };
+                self.last_edited = index;
+                self.column_changed(index);
+                self.spaces[index] = self.spaces[index].convert(selected);
+                self.save_spaces();
+            }
+            Message::ChangeLayout { index, layout } => {
+                self.spaces[index].set_layout(PickerLayout::ALL[layout]);
+                self.save_spaces();
+            }
+            Message::ChangeHex { index, string } => {
+                self.column_changed(index);
+                if let Some(rgba) = hex::parse_hex(&string) {
+                    self.spaces[index].from_rgb(rgba);
+                }
+
+                self.last_edited = index;
+                self.hex_edit = Some((index, string));
             }
             Message::AddSpace => {
-                self.spaces.push(ColorSpace::default());
+                self.spaces.push(self.new_space());
+                self.update_column_names();
+                self.save_spaces();
             }
             Message::RemoveSpace(index) => {
+                self.hex_edit = None;
+                self.copy_notice = None;
                 self.spaces.remove(index);
+                for column in self.column_indices() {
+                    if *column >= index && *column > 0 {
+                        *column -= 1;
+                    }
+                }
+                self.update_column_names();
+                self.save_spaces();
+            }
+            Message::MapIntoGamut(index) => {
+                self.last_edited = index;
+                self.column_changed(index);
+                self.spaces[index].map_into_gamut();
+                self.save_spaces();
+            }
+            Message::OpenHarmony(index) => {
+                self.harmony_source = index;
+                self.context_page = ContextPage::Harmony;
+                self.core.window.show_context = true;
+            }
+            Message::ChangeHarmony(index) => {
+                self.harmony = Harmony::ALL[index];
+                self.harmony_spread = self.harmony.default_spread().unwrap_or(0.0);
+            }
+            Message::ChangeHarmonySpread(spread) => {
+                self.harmony_spread = spread;
+            }
+            Message::AddHarmony => {
+                let Some(source) = self.spaces.get(self.harmony_source) else {
+                    return Task::none();
+                };
+
+                // Right after the source, in its color space
+                let index = self.harmony_source + 1;
+                let spaces: Vec<ColorSpace> =
+                    harmony::generate(source.get_rgb(), self.harmony, self.harmony_spread)
+                        .into_iter()
+                        .map(|rgb| {
+                            let mut space = source.clone();
+                            space.from_rgb(rgb);
+                            space
+                        })
+                        .collect();
+                let count = spaces.len();
+                self.spaces.splice(index..index, spaces);
+                for column in self.column_indices() {
+                    if *column >= index {
+                        *column += count;
+                    }
+                }
+
+                self.hex_edit = None;
+                self.copy_notice = None;
+                self.last_edited = self.harmony_source;
+                self.update_column_names();
+                self.save_spaces();
+            }
+            Message::ChangeScaleSource(index) => {
+                self.scale_source = index;
+            }
+            Message::ChangeScaleSteps(index) => {
+                self.scale.steps = index + MIN_STEPS;
+            }
+            Message::ChangeScaleSpace(index) => {
+                self.scale.space = ScaleSpace::ALL[index];
+            }
+            Message::ChangeScaleEasing(index) => {
+                self.scale.easing = Easing::ALL[index];
+            }
+            Message::ToggleScaleChroma(vary_chroma) => {
+                self.scale.vary_chroma = vary_chroma;
+            }
+            Message::ChangeScaleName(name) => {
+                self.scale_name = name;
+            }
+            Message::ChangeScaleExport(index) => {
+                self.scale_export = PaletteFormat::ALL[index];
+            }
+            Message::AppendScale => {
+                let Some(source) = self.spaces.get(self.scale_source) else {
+                    return Task::none();
+                };
+
+                let spaces: Vec<ColorSpace> = self
+                    .scale
+                    .generate(source.get_rgb())
+                    .into_iter()
+                    .map(|rgb| {
+                        let mut space = source.clone();
+                        space.from_rgb(rgb);
+                        space
+                    })
+                    .collect();
+                self.spaces.extend(spaces);
+                self.update_column_names();
+                self.save_spaces();
+            }
+            Message::ExportScale => {
+                let Some(source) = self.spaces.get(self.scale_source) else {
+                    return Task::none();
+                };
+
+                let colors = self.scale.generate(source.get_rgb());
+                let steps = scale::step_names(colors.len());
+                let contents = self.scale_export.export(&self.scale_name, &steps, &colors);
+                info!("Copying the {} palette to clipboard", self.scale_name);
+                return clipboard::write(contents);
+            }
+            Message::ChangeGradientFirst(index) => {
+                self.gradient_first = index;
+            }
+            Message::ChangeGradientLast(index) => {
+                self.gradient_last = index;
+            }
+            Message::ChangeGradientInterpolation(index) => {
+                self.gradient_interpolation = Interpolation::ALL[index];
+            }
+            Message::ChangeGradientHue(index) => {
+                self.gradient_hue = HueMethod::ALL[index];
+            }
+            Message::ChangeGradientSamples(index) => {
+                self.gradient_samples = index + MIN_STEPS;
+            }
+            Message::CopyGradientCss => {
+                let stops = self.gradient_stops();
+                if stops.len() < 2 {
+                    return Task::none();
+                }
+
+                let contents =
+                    gradient::to_css(&stops, self.gradient_interpolation, self.gradient_hue);
+                info!("Copying \"{}\" to clipboard", contents);
+                return clipboard::write(contents);
+            }
+            Message::CopyGradientSamples => {
+                let stops = self.gradient_stops();
+                if stops.len() < 2 {
+                    return Task::none();
+                }
+
+                let samples = gradient::samples(
+                    &stops,
+                    self.gradient_interpolation,
+                    self.gradient_hue,
+                    self.gradient_samples,
+                );
+                let contents: Vec<String> = samples.into_iter().map(hex::to_hex).collect();
+                let contents = contents.join("\n");
+                info!("Copying \"{}\" to clipboard", contents);
+                return clipboard::write(contents);
             }
 
             Message::ToggleGraphs => {
                 self.show_graphs = !self.show_graphs;
             }
-            Message::ToggleAboutPage => {
-                self.core.window.show_context = !self.core.window.show_context;
+            Message::ToggleContextPage(page) => {
+                if self.context_page == page {
+                    self.core.window.show_context = !self.core.window.show_context;
+                } else {
+                    self.context_page = page;
+                    self.core.window.show_context = true;
+                }
             }
             Message::LaunchUrl(url) => match open::that_detached(&url) {
                 Ok(()) => {}
@@ -185,9 +639,127 @@ impl Application for ColorPicker {
                 }
             },
 
+            Message::SelectCopyFormat { space, format } => {
+                self.copy_formats.insert(space.to_string(), format);
+            }
+            Message::AddCopyFormat => {
+                self.custom_formats.push(String::new());
+                self.update_copy_format_names();
+            }
+            Message::ChangeCopyFormat { index, template } => {
+                self.custom_formats[index] = template;
+                self.update_copy_format_names();
+            }
+            Message::RemoveCopyFormat(index) => {
+                self.custom_formats.remove(index);
+                for (space, format) in self.copy_formats.iter_mut() {
+                    let Some(model) = colorspace::model(space) else {
+                        continue;
+                    };
+
+                    let custom = index + model.copy_formats().len();
+                    if *format > custom {
+                        *format -= 1;
+                    } else if *format == custom {
+                        *format = 0;
+                    }
+                }
+                self.update_copy_format_names();
+            }
+            Message::ChangeDefaultSpace(index) => {
+                self.default_space = self.colorspace_selections[index];
+            }
+            Message::ChangePrecision(precision) => {
+                self.precision = precision;
+            }
+            Message::ChangeOutOfGamutCopy(index) => {
+                self.out_of_gamut_copy = OutOfGamutCopy::ALL[index];
+            }
+            Message::ToggleSnapSliders(snap) => {
+                self.snap_sliders = snap;
+            }
+            Message::ChangeContrastForeground(index) => {
+                self.contrast_foreground = index;
+            }
+            Message::ChangeContrastBackground(index) => {
+                self.contrast_background = index;
+            }
+            Message::ChangeDeltaE(index) => {
+                self.delta_e = DeltaE::ALL[index];
+                self.delta_e_threshold = self.delta_e.just_noticeable();
+                self.delta_e_threshold_string = self.delta_e_threshold.to_string();
+            }
+            Message::ChangeDeltaEThreshold(string) => {
+                if let Ok(threshold) = string.parse::<f32>() {
+                    self.delta_e_threshold = threshold.max(0.0);
+                }
+                self.delta_e_threshold_string = string;
+            }
+            Message::ChangeDeficiency(index) => {
+                // The first entry is normal vision
+                self.deficiency = index.checked_sub(1).map(|index| Deficiency::ALL[index]);
+            }
+            Message::ChangeDeficiencySeverity(severity) => {
+                self.deficiency_severity = severity;
+            }
+            Message::ToggleSimulateView(simulate_view) => {
+                self.simulate_view = simulate_view;
+            }
+            Message::UpdateConfig(mut config) => {
+                // The watcher also echoes the saves of this instance, which
+                // may be behind the columns by now. Any other columns were
+                // saved by another instance.
+                let echo = self
+                    .unechoed_spaces
+                    .iter()
+                    .position(|spaces| *spaces == config.spaces);
+                let spaces_changed = match echo {
+                    Some(index) => {
+                        self.unechoed_spaces.drain(..=index);
+                        config.spaces = self.config.spaces.clone();
+                        false
+                    }
+                    None => config.spaces != self.config.spaces,
+                };
+
+                if config != self.config {
+                    self.apply_config(config);
+                }
+                if spaces_changed {
+                    self.unechoed_spaces.clear();
+                    self.spaces_changed = false;
+                    self.load_spaces();
+                }
+                return Task::none();
+            }
+            Message::SaveSpaces => {
+                self.save_spaces();
+                return Task::none();
+            }
             Message::CopyToClipboard(index) => {
                 return self.copy_to_clipboard(index);
             }
+            Message::Paste(contents) => {
+                let Some(contents) = contents else {
+                    return Task::none();
+                };
+
+                let Some(color) = css::parse_css(&contents) else {
+                    log::warn!("Clipboard doesn't contain a CSS color: {:?}", contents);
+                    return Task::none();
+                };
+
+                if self.spaces.is_empty() {
+                    self.spaces.push(self.new_space());
+                    self.update_column_names();
+                }
+
+                let index = self.last_edited.min(self.spaces.len() - 1);
+                self.last_edited = index;
+                self.column_changed(index);
+                self.spaces[index].from_css(&color);
+                self.save_spaces();
+            }
             Message::PickScreenRequest(index) => {
                 return cosmic::task::future(async move {
                     let req = ashpd::desktop::Color::pick().send().await;
@@ -209,7 +781,11 @@ impl Application for ColorPicker {
             }
             Message::PickScreenResponse((index, color)) => {
                 let (r, g, b) = (color.red(), color.green(), color.blue());
-                self.spaces[index].from_rgb([r as f32, g as f32, b as f32])
+                let alpha = self.spaces[index].get_rgb()[3];
+                self.last_edited = index;
+                self.column_changed(index);
+                self.spaces[index].from_rgb([r as f32, g as f32, b as f32, alpha]);
+                self.save_spaces();
             }
             Message::Key(key, modifiers) => {
                 for (key_bind, action) in self.keybinds.iter() {
@@ -221,67 +797,147 @@ impl Application for ColorPicker {
                 if modifiers.control() && key == Key::Character("c".into()) {
                     return self.copy_to_clipboard(self.last_edited);
                 }
+
+                if modifiers.control() && key == Key::Character("v".into()) {
+                    return clipboard::read().map(|contents| Message::Paste(contents).into());
+                }
             }
         }
 
+        self.save_config();
         Task::none()
     }
 
     fn view(&self) -> Element<Self::Message> {
         let mut contents = widget::row::with_capacity(self.spaces.len());
+        let simulation = self.simulation();
+        let view_simulation = self.view_simulation();
+        let simulate = |color: Color| match view_simulation {
+            Some(simulation) => simulation.apply_color(color),
+            None => color,
+        };
 
         for (colorspace, index) in self.spaces.iter().zip(0..) {
-            let (rgb, content, combo_selection) = match colorspace {
-                ColorSpace::Rgb(rgb) => (
-                    rgb.to_rgb(),
-                    rgb.view(self.show_graphs),
-                    0, //ColorSpaceCombo::Rgb,
-                ),
-                ColorSpace::Hsv(hsv) => (
-                    hsv.to_rgb(),
-                    hsv.view(self.show_graphs),
-                    1, //ColorSpaceCombo::Hsv,
-                ),
-                ColorSpace::Oklab(oklab) => (
-                    oklab.to_rgb(),
-                    oklab.view(self.show_graphs),
-                    2, //ColorSpaceCombo::Oklab,
-                ),
-                ColorSpace::Oklch(oklch) => (
-                    oklch.to_rgb(),
-                    oklch.view(self.show_graphs),
-                    3, //ColorSpaceCombo::Oklch,
-                ),
-                ColorSpace::Cmyk(cmyk) => (
-                    cmyk.to_rgb(),
-                    cmyk.view(self.show_graphs),
-                    4, //ColorSpaceCombo::Cmyk,
+            let model = colorspace.model();
+            let rgb = colorspace.get_rgb();
+            let content =
+                colorspace.view(index, self.show_graphs, view_simulation, self.snap_sliders);
+            let model_selection = self
+                .colorspace_selections
+                .iter()
+                .position(|selection| selection.id() == model.id());
+            let layout_selection = PickerLayout::ALL
+                .iter()
+                .position(|layout| *layout == colorspace.layout());
+
+            let mapped = gamut::map_into_gamut([rgb[0], rgb[1], rgb[2]], RgbSpace::Srgb);
+            let in_gamut = colorspace.in_gamut();
+
+            let (hex_string, hex_valid) = match &self.hex_edit {
+                Some((edit_index, string)) if *edit_index == index => {
+                    (string.clone(), hex::parse_hex(string).is_some())
+                }
+                _ => (hex::to_hex(rgb), true),
+            };
+
+            let mut hex_input = widget::column::with_capacity(2).push(
+                widget::row::with_capacity(2)
+                    .push(widget::text(fl!("hex")))
+                    .push(
+                        widget::text_input("#RRGGBB", hex_string)
+                            .on_input(move |string| Message::ChangeHex { index, string }),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(10.0),
+            );
+            if !hex_valid {
+                hex_input = hex_input.push(
+                    widget::row::with_capacity(2)
+                        .push(
+                            widget::icon::from_name("dialog-error-symbolic")
+                                .size(16)
+                                .icon(),
+                        )
+                        .push(widget::text::caption(fl!("invalid-hex")))
+                        .align_y(Alignment::Center)
+                        .spacing(5.0),
+                );
+            }
+
+            let gamut = Gamut::of([rgb[0], rgb[1], rgb[2]]);
+            let gamut_status = widget::row::with_capacity(2)
+                .push_maybe((gamut != Gamut::Srgb).then(|| {
+                    widget::icon::from_name("dialog-warning-symbolic")
+                        .size(16)
+                        .icon()
+                }))
+                .push(widget::text::caption(match gamut {
+                    Gamut::Srgb => fl!("gamut-srgb"),
+                    Gamut::DisplayP3 => fl!("gamut-display-p3"),
+                    Gamut::Rec2020 => fl!("gamut-rec2020"),
+                    Gamut::Out => fl!("gamut-out"),
+                }))
+                .align_y(Alignment::Center)
+                .spacing(5.0);
+
+            let copy_notice = match &self.copy_notice {
+                Some((notice_index, notice)) if *notice_index == index => Some(
+                    widget::row::with_capacity(2)
+                        .push(
+                            widget::icon::from_name("dialog-information-symbolic")
+                                .size(16)
+                                .icon(),
+                        )
+                        .push(widget::text::caption(notice.clone()))
+                        .align_y(Alignment::Center)
+                        .spacing(5.0),
                 ),
+                _ => None,
             };
 
-            let min_rgb = rgb[0].min(rgb[1]).min(rgb[2]).min(0.0);
-            let max_rgb = rgb[0].max(rgb[1]).max(rgb[2]).max(1.0) - min_rgb;
-            let norm_rgb = [
-                (rgb[0] - min_rgb) / max_rgb,
-                (rgb[1] - min_rgb) / max_rgb,
-                (rgb[2] - min_rgb) / max_rgb,
-            ];
+            let color = Color::from_rgba(rgb[0], rgb[1], rgb[2], rgb[3]);
+            let mapped = Color::from_rgba(mapped[0], mapped[1], mapped[2], rgb[3]);
+
+            // Below the swatches unless every column is already simulated
+            let simulated = simulation
+                .filter(|_| !self.simulate_view)
+                .map(|simulation| {
+                    widget::column::with_capacity(2)
+                        .push(widget::text::caption(fl!("simulated")))
+                        .push(
+                            widget::row::with_capacity(2)
+                                .push(
+                                    color_block(simulation.apply_color(color))
+                                        .border([true, false, false, true])
+                                        .height(40.0),
+                                )
+                                .push(
+                                    color_block(simulation.apply_color(mapped))
+                                        .border([false, true, true, false])
+                                        .height(40.0),
+                                ),
+                        )
+                        .spacing(5.0)
+                });
 
             let sidebar = widget::Container::new(
-                widget::column::with_capacity(3)
+                widget::column::with_capacity(8)
                     .push(
                         widget::row::with_capacity(2)
                             .push(
-                                color_block(Color::from_rgb(rgb[0], rgb[1], rgb[2]))
+                                color_block(simulate(color))
                                     .border([true, false, false, true])
                                     .height(100.0),
                             )
                             .push(
-                                color_block(Color::from_rgb(norm_rgb[0], norm_rgb[1], norm_rgb[2]))
+                                color_block(simulate(mapped))
                                     .border([false, true, true, false])
                                     .height(100.0),
                             ),
                     )
+                    .push_maybe(simulated)
+                    .push(gamut_status)
+                    .push(hex_input.spacing(5.0))
                     .push(
                         widget::row::with_capacity(3)
                             .push(
@@ -289,12 +945,36 @@ impl Application for ColorPicker {
                                     .on_press(Message::CopyToClipboard(index))
                                     .tooltip("Copy to Clipboard"),
                             )
+                            .push(
+                                widget::dropdown(
+                                    &self.copy_format_names[model.id()],
+                                    Some(self.copy_format(model)),
+                                    move |format| Message::SelectCopyFormat {
+                                        space: model.id(),
+                                        format,
+                                    },
+                                )
+                                .width(Length::Fill),
+                            )
+                            .push(
+                                widget::button::icon(widget::icon::from_name(
+                                    "zoom-fit-best-symbolic",
+                                ))
+                                .on_press_maybe((!in_gamut).then_some(Message::MapIntoGamut(index)))
+                                .tooltip("Map into gamut"),
+                            )
                             .push(
                                 widget::button::icon(widget::icon::from_name("edit-find-symbolic"))
                                     .on_press(Message::PickScreenRequest(index))
                                     .tooltip("Pick a color from the screen"),
                             )
-                            .push(widget::Space::with_width(Length::Fill))
+                            .push(
+                                widget::button::icon(widget::icon::from_name(
+                                    "applications-graphics-symbolic",
+                                ))
+                                .on_press(Message::OpenHarmony(index))
+                                .tooltip("Harmony"),
+                            )
                             .push(
                                 widget::button::icon(widget::icon::from_name(
                                     "user-trash-full-symbolic",
@@ -304,15 +984,23 @@ impl Application for ColorPicker {
                                 .tooltip("Delete"),
                             ),
                     )
+                    .push_maybe(copy_notice)
                     .push(
-                        widget::dropdown(&self.colorspace_names, Some(combo_selection), move |t| {
+                        widget::dropdown(&self.colorspace_names, model_selection, move |t| {
                             Message::ChangeColorSpace {
                                 index,
-                                selected: self.colorspace_selections[t].clone(),
+                                selected: self.colorspace_selections[t],
                             }
                         })
                         .width(Length::Fill),
                     )
+                    // The pickers are drawn by shaders
+                    .push_maybe(shaders::gpu_available().then(|| {
+                        widget::dropdown(&self.layout_names, layout_selection, move |layout| {
+                            Message::ChangeLayout { index, layout }
+                        })
+                        .width(Length::Fill)
+                    }))
                     .spacing(10.0),
             )
             .class(theme::Container::Card)
@@ -343,6 +1031,8 @@ impl Application for ColorPicker {
             );
         }
 
+        contents = contents.push(renderer_probe(Message::None));
+
         widget::scrollable(contents)
             .direction(Direction::Horizontal(Scrollbar::new()))
             .height(Length::Fill)
@@ -354,36 +1044,901 @@ impl Application for ColorPicker {
             return None;
         }
 
-        Some(self.about())
+        Some(match self.context_page {
+            ContextPage::About => self.about(),
+            ContextPage::CopyFormats => self.copy_formats_page(),
+            ContextPage::Settings => self.settings_page(),
+            ContextPage::Contrast => self.contrast_page(),
+            ContextPage::Difference => self.difference_page(),
+            ContextPage::ColorVision => self.color_vision_page(),
+            ContextPage::Harmony => self.harmony_page(),
+            ContextPage::Scale => self.scale_page(),
+            ContextPage::Gradient => self.gradient_page(),
+        })
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
-        Subscription::batch(vec![event::listen_with(
-            |event, status, _windowid| match event {
+        let save_spaces = self
+            .spaces_changed
+            .then(|| time::every(SPACES_SAVE_INTERVAL).map(|_| Message::SaveSpaces));
+
+        Subscription::batch(vec![
+            event::listen_with(|event, status, _windowid| match event {
                 Event::Keyboard(KeyEvent::KeyPressed { key, modifiers, .. }) => match status {
                     event::Status::Ignored => Some(Message::Key(key, modifiers)),
                     event::Status::Captured => None,
                 },
                 _ => None,
-            },
-        )])
+            }),
+            self.core()
+                .watch_config::<Config>(Self::APP_ID)
+                .map(|update| {
+                    for err in update.errors {
+                        log::warn!("Failed to load config: {}", err);
+                    }
+                    Message::UpdateConfig(update.config)
+                }),
+            save_spaces.unwrap_or_else(Subscription::none),
+        ])
     }
 }
 
 impl ColorPicker {
-    fn copy_to_clipboard(&self, index: usize) -> Task<Message> {
-        let contents = match &self.spaces[index] {
-            ColorSpace::Rgb(rgb) => rgb.copy_to_clipboard(),
-            ColorSpace::Hsv(hsv) => hsv.copy_to_clipboard(),
-            ColorSpace::Oklab(oklab) => oklab.copy_to_clipboard(),
-            ColorSpace::Oklch(oklch) => oklch.copy_to_clipboard(),
-            ColorSpace::Cmyk(cmyk) => cmyk.copy_to_clipboard(),
+    fn new_space(&self) -> ColorSpace {
+        ColorSpace::default().convert(self.default_space)
+    }
+
+    /// Loads the settings of `config`, the columns are loaded by
+    /// [`Self::load_spaces`].
+    fn apply_config(&mut self, config: Config) {
+        self.show_graphs = config.show_graphs;
+        self.snap_sliders = config.snap_sliders;
+        self.default_space = colorspace::model(&config.default_space)
+            .unwrap_or_else(|| ColorSpace::default().model());
+        self.copy_formats = config.copy_formats.clone();
+        self.custom_formats = config.custom_formats.clone();
+        self.precision = config.precision.min(config::MAX_PRECISION);
+        self.out_of_gamut_copy = config.out_of_gamut_copy;
+        self.delta_e = config.delta_e;
+        if self.delta_e_threshold != config.delta_e_threshold {
+            self.delta_e_threshold = config.delta_e_threshold;
+            self.delta_e_threshold_string = config.delta_e_threshold.to_string();
+        }
+        self.update_copy_format_names();
+        self.config = config;
+    }
+
+    /// Replaces the columns with the saved ones
+    fn load_spaces(&mut self) {
+        self.spaces = self
+            .config
+            .spaces
+            .iter()
+            .filter_map(SavedSpace::to_space)
+            .collect();
+        self.hex_edit = None;
+        self.copy_notice = None;
+        let last = self.spaces.len().saturating_sub(1);
+        for column in self.column_indices() {
+            *column = (*column).min(last);
+        }
+        self.update_column_names();
+    }
+
+    fn save_config(&mut self) {
+        let Some(handler) = &self.config_handler else {
+            return;
+        };
+
+        let results = [
+            self.config.set_show_graphs(handler, self.show_graphs),
+            self.config.set_snap_sliders(handler, self.snap_sliders),
+            self.config
+                .set_default_space(handler, self.default_space.id().to_string()),
+            self.config
+                .set_copy_formats(handler, self.copy_formats.clone()),
+            self.config
+                .set_custom_formats(handler, self.custom_formats.clone()),
+            self.config.set_precision(handler, self.precision),
+            self.config
+                .set_out_of_gamut_copy(handler, self.out_of_gamut_copy),
+            self.config.set_delta_e(handler, self.delta_e),
+            self.config
+                .set_delta_e_threshold(handler, self.delta_e_threshold),
+        ];
+
+        for err in results.into_iter().filter_map(Result::err) {
+            log::warn!("Failed to save config: {}", err);
+        }
+
+        let spaces: Vec<SavedSpace> = self.spaces.iter().map(SavedSpace::from).collect();
+        self.spaces_changed = spaces != self.config.spaces;
+    }
+
+    fn save_spaces(&mut self) {
+        self.spaces_changed = false;
+        let Some(handler) = &self.config_handler else {
+            return;
+        };
+
+        let spaces: Vec<SavedSpace> = self.spaces.iter().map(SavedSpace::from).collect();
+        if spaces == self.config.spaces {
+            return;
+        }
+
+        if let Err(err) = self.config.set_spaces(handler, spaces.clone()) {
+            log::warn!("Failed to save config: {}", err);
+            return;
+        }
+
+        if self.unechoed_spaces.len() == MAX_UNECHOED_SPACES {
+            self.unechoed_spaces.remove(0);
+        }
+        self.unechoed_spaces.push(spaces);
+    }
+
+    /// The columns picked on the pages, to follow their colors when columns
+    /// are removed or inserted
+    fn column_indices(&mut self) -> [&mut usize; 7] {
+        [
+            &mut self.last_edited,
+            &mut self.harmony_source,
+            &mut self.scale_source,
+            &mut self.contrast_foreground,
+            &mut self.contrast_background,
+            &mut self.gradient_first,
+            &mut self.gradient_last,
+        ]
+    }
+
+    /// Drops the typed hex field and the copy notice of a column whose
+    /// color changed.
+    fn column_changed(&mut self, index: usize) {
+        if matches!(self.hex_edit, Some((edit_index, _)) if edit_index == index) {
+            self.hex_edit = None;
+        }
+        if matches!(self.copy_notice, Some((notice_index, _)) if notice_index == index) {
+            self.copy_notice = None;
+        }
+    }
+
+    fn copy_format(&self, model: &dyn ColorModel) -> usize {
+        let count = model.copy_formats().len() + self.custom_formats.len();
+        self.copy_formats
+            .get(model.id())
+            .copied()
+            .unwrap_or(0)
+            .min(count - 1)
+    }
+
+    fn copy_template(&self, model: &dyn ColorModel) -> &str {
+        let builtin = model.copy_formats();
+        match self.copy_format(model) {
+            format if format < builtin.len() => builtin[format],
+            format => &self.custom_formats[format - builtin.len()],
+        }
+    }
+
+    fn update_copy_format_names(&mut self) {
+        self.copy_format_names = self
+            .colorspace_selections
+            .iter()
+            .map(|model| {
+                let names = model
+                    .copy_formats()
+                    .iter()
+                    .map(|format| format.to_string())
+                    .chain(self.custom_formats.iter().cloned())
+                    .collect();
+                (model.id(), names)
+            })
+            .collect();
+    }
+
+    fn copy_to_clipboard(&mut self, index: usize) -> Task<Message> {
+        let Some(space) = self.spaces.get(index) else {
+            return Task::none();
         };
 
+        let mut space = space.clone();
+        self.copy_notice = None;
+        if !space.in_gamut() {
+            match self.out_of_gamut_copy {
+                OutOfGamutCopy::Copy => {}
+                OutOfGamutCopy::Clamp => {
+                    space.clip_into_gamut();
+                    self.copy_notice = Some((index, fl!("copy-clamped")));
+                }
+                OutOfGamutCopy::Refuse => {
+                    self.copy_notice = Some((index, fl!("copy-refused")));
+                    return Task::none();
+                }
+            }
+        }
+
+        let contents = space.format(self.copy_template(space.model()), self.precision);
         info!("Copying \"{}\" to clipboard", contents);
         clipboard::write(contents)
     }
 
+    fn settings_page(&self) -> ContextDrawer<Message> {
+        let default_space = self
+            .colorspace_selections
+            .iter()
+            .position(|model| model.id() == self.default_space.id());
+
+        let content = widget::settings::section()
+            .add(widget::settings::item(
+                fl!("default-space"),
+                widget::dropdown(
+                    &self.colorspace_names,
+                    default_space,
+                    Message::ChangeDefaultSpace,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("precision"),
+                widget::dropdown(&PRECISIONS, Some(self.precision), Message::ChangePrecision),
+            ))
+            .add(widget::settings::item(
+                fl!("out-of-gamut"),
+                widget::dropdown(
+                    &self.out_of_gamut_copy_names,
+                    OutOfGamutCopy::ALL
+                        .iter()
+                        .position(|copy| *copy == self.out_of_gamut_copy),
+                    Message::ChangeOutOfGamutCopy,
+                ),
+            ))
+            .add(
+                widget::settings::item::builder(fl!("snap-sliders"))
+                    .description(fl!("snap-sliders-description"))
+                    .toggler(self.snap_sliders, Message::ToggleSnapSliders),
+            );
+
+        ContextDrawer {
+            title: Some(fl!("settings").into()),
+            header_actions: vec![],
+            header: None,
+            content: content.into(),
+            footer: None,
+            on_close: Message::ToggleContextPage(ContextPage::Settings),
+        }
+    }
+
+    fn update_column_names(&mut self) {
+        self.column_names = (1..=self.spaces.len())
+            .map(|column| fl!("column", number = column))
+            .collect();
+    }
+
+    fn contrast_page(&self) -> ContextDrawer<Message> {
+        let last = self.spaces.len().saturating_sub(1);
+        let foreground = self.contrast_foreground.min(last);
+        let background = self.contrast_background.min(last);
+
+        let mut content = widget::column::with_capacity(3).spacing(10.0);
+        content = content.push(
+            widget::settings::section()
+                .add(widget::settings::item(
+                    fl!("foreground"),
+                    widget::dropdown(
+                        &self.column_names,
+                        Some(foreground),
+                        Message::ChangeContrastForeground,
+                    ),
+                ))
+                .add(widget::settings::item(
+                    fl!("background"),
+                    widget::dropdown(
+                        &self.column_names,
+                        Some(background),
+                        Message::ChangeContrastBackground,
+                    ),
+                )),
+        );
+
+        if let (Some(fg), Some(bg)) = (self.spaces.get(foreground), self.spaces.get(background)) {
+            let (fg, bg) = (fg.get_rgb(), bg.get_rgb());
+            let ratio = contrast::contrast_ratio(fg, bg);
+            let luminance = |rgb: [f32; 4]| {
+                let rgb = contrast::composite(rgb, [bg[0], bg[1], bg[2]]);
+                format::format_number(contrast::relative_luminance(rgb), Some(4), 0)
+            };
+
+            let level = |name: String, minimum: f32| {
+                let (icon, result) = if ratio >= minimum {
+                    ("emblem-ok-symbolic", fl!("pass"))
+                } else {
+                    ("dialog-error-symbolic", fl!("fail"))
+                };
+
+                widget::settings::item(
+                    name,
+                    widget::row::with_capacity(2)
+                        .push(widget::icon::from_name(icon).size(16).icon())
+                        .push(widget::text::body(result))
+                        .align_y(Alignment::Center)
+                        .spacing(5.0),
+                )
+            };
+
+            content = content
+                .push(
+                    widget::settings::section()
+                        .add(widget::settings::item(
+                            fl!("contrast-ratio"),
+                            widget::text::title4(format!(
+                                "{}:1",
+                                format::format_number(ratio, Some(2), 0)
+                            )),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("foreground-luminance"),
+                            widget::text::body(luminance(fg)),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("background-luminance"),
+                            widget::text::body(luminance([bg[0], bg[1], bg[2], 1.0])),
+                        ))
+                        .add(level(fl!("aa-normal"), contrast::AA_NORMAL))
+                        .add(level(fl!("aaa-normal"), contrast::AAA_NORMAL))
+                        .add(level(fl!("aa-large"), contrast::AA_LARGE))
+                        .add(level(fl!("aaa-large"), contrast::AAA_LARGE)),
+                )
+                .push(apca_section(fg, bg))
+                .push(contrast_preview(fg, bg));
+        }
+
+        ContextDrawer {
+            title: Some(fl!("contrast").into()),
+            header_actions: vec![],
+            header: None,
+            content: content.into(),
+            footer: None,
+            on_close: Message::ToggleContextPage(ContextPage::Contrast),
+        }
+    }
+
+    fn difference_page(&self) -> ContextDrawer<Message> {
+        const CELL_WIDTH: f32 = 64.0;
+
+        let delta_e = DeltaE::ALL
+            .iter()
+            .position(|delta_e| *delta_e == self.delta_e);
+        let settings = widget::settings::section()
+            .add(widget::settings::item(
+                fl!("difference-metric"),
+                widget::dropdown(&DELTA_E_NAMES, delta_e, Message::ChangeDeltaE),
+            ))
+            .add(widget::settings::item(
+                fl!("difference-threshold"),
+                widget::text_input("", self.delta_e_threshold_string.clone())
+                    .on_input(Message::ChangeDeltaEThreshold)
+                    .width(CELL_WIDTH * 1.5),
+            ));
+
+        let rgb: Vec<[f32; 3]> = self
+            .spaces
+            .iter()
+            .map(|space| {
+                let rgb = space.get_rgb();
+                [rgb[0], rgb[1], rgb[2]]
+            })
+            .collect();
+        let header = |index: usize| {
+            let [r, g, b] = rgb[index];
+            widget::column::with_capacity(2)
+                .push(widget::text::caption((index + 1).to_string()))
+                .push(color_block(Color::from_rgb(r, g, b)).height(16.0))
+                .align_x(Alignment::Center)
+                .spacing(2.0)
+                .width(CELL_WIDTH)
+        };
+
+        let mut top = widget::row::with_capacity(rgb.len() + 1)
+            .push(widget::horizontal_space().width(CELL_WIDTH));
+        for column in 0..rgb.len() {
+            top = top.push(header(column));
+        }
+
+        let precision = if self.delta_e == DeltaE::Ok { 3 } else { 2 };
+        let mut matrix = widget::column::with_capacity(rgb.len() + 1)
+            .push(top)
+            .spacing(5.0);
+        for row in 0..rgb.len() {
+            let mut cells = widget::row::with_capacity(rgb.len() + 1)
+                .push(header(row))
+                .align_y(Alignment::Center);
+            for column in 0..rgb.len() {
+                let cell: Element<Message> = if row == column {
+                    widget::text::body("—").into()
+                } else {
+                    let difference = self.delta_e.between(rgb[row], rgb[column]);
+                    let value =
+                        widget::text::body(format::format_number(difference, Some(precision), 0));
+                    if difference < self.delta_e_threshold {
+                        widget::row::with_capacity(2)
+                            .push(
+                                widget::icon::from_name("emblem-ok-symbolic")
+                                    .size(12)
+                                    .icon(),
+                            )
+                            .push(value)
+                            .align_y(Alignment::Center)
+                            .spacing(2.0)
+                            .into()
+                    } else {
+                        value.into()
+                    }
+                };
+
+                cells = cells.push(
+                    widget::container(cell)
+                        .align_x(Horizontal::Center)
+                        .width(CELL_WIDTH),
+                );
+            }
+            matrix = matrix.push(cells);
+        }
+
+        let content = widget::column::with_capacity(3)
+            .push(settings)
+            .push(widget::scrollable(matrix).direction(Direction::Horizontal(Scrollbar::new())))
+            .push(widget::text::caption(fl!("difference-identical")))
+            .spacing(10.0);
+
+        ContextDrawer {
+            title: Some(fl!("difference").into()),
+            header_actions: vec![],
+            header: None,
+            content: content.into(),
+            footer: None,
+            on_close: Message::ToggleContextPage(ContextPage::Difference),
+        }
+    }
+
+    /// The selected deficiency at its severity
+    fn simulation(&self) -> Option<Simulation> {
+        self.deficiency.map(|deficiency| Simulation {
+            deficiency,
+            severity: self.deficiency_severity,
+        })
+    }
+
+    /// The selected deficiency when every column is simulated
+    fn view_simulation(&self) -> Option<Simulation> {
+        self.simulation().filter(|_| self.simulate_view)
+    }
+
+    fn color_vision_page(&self) -> ContextDrawer<Message> {
+        let selected = match self.deficiency {
+            Some(deficiency) => Deficiency::ALL
+                .iter()
+                .position(|d| *d == deficiency)
+                .map(|index| index + 1),
+            None => Some(0),
+        };
+
+        let mut section = widget::settings::section().add(widget::settings::item(
+            fl!("deficiency"),
+            widget::dropdown(&self.deficiency_names, selected, Message::ChangeDeficiency),
+        ));
+
+        if let Some(deficiency) = self.deficiency {
+            // Anomalous trichromacy below full severity, dichromacy at it
+            let dichromacy = self.deficiency_severity >= 1.0;
+            let kind = match (deficiency, dichromacy) {
+                (Deficiency::Protan, false) => fl!("protanomaly"),
+                (Deficiency::Protan, true) => fl!("protanopia"),
+                (Deficiency::Deutan, false) => fl!("deuteranomaly"),
+                (Deficiency::Deutan, true) => fl!("deuteranopia"),
+                (Deficiency::Tritan, false) => fl!("tritanomaly"),
+                (Deficiency::Tritan, true) => fl!("tritanopia"),
+            };
+
+            section = section.add(
+                widget::settings::item::builder(fl!("severity"))
+                    .description(format!(
+                        "{kind}, {}%",
+                        (self.deficiency_severity * 100.0).round()
+                    ))
+                    .control(
+                        widget::slider(
+                            0.0..=1.0,
+                            self.deficiency_severity,
+                            Message::ChangeDeficiencySeverity,
+                        )
+                        .step(0.01),
+                    ),
+            );
+        }
+
+        section = section.add(
+            widget::settings::item::builder(fl!("simulate-view"))
+                .description(fl!("simulate-view-description"))
+                .toggler(self.simulate_view, Message::ToggleSimulateView),
+        );
+
+        ContextDrawer {
+            title: Some(fl!("color-vision").into()),
+            header_actions: vec![],
+            header: None,
+            content: section.into(),
+            footer: None,
+            on_close: Message::ToggleContextPage(ContextPage::ColorVision),
+        }
+    }
+
+    fn harmony_page(&self) -> ContextDrawer<Message> {
+        let selected = Harmony::ALL
+            .iter()
+            .position(|harmony| *harmony == self.harmony);
+        let mut section = widget::settings::section().add(widget::settings::item(
+            fl!("harmony-rule"),
+            widget::dropdown(&self.harmony_names, selected, Message::ChangeHarmony),
+        ));
+
+        if self.harmony.default_spread().is_some() {
+            section = section.add(
+                widget::settings::item::builder(fl!("harmony-spread"))
+                    .description(format!("{}°", self.harmony_spread.round()))
+                    .control(
+                        widget::slider(
+                            0.0..=180.0,
+                            self.harmony_spread,
+                            Message::ChangeHarmonySpread,
+                        )
+                        .step(1.0),
+                    ),
+            );
+        }
+
+        let mut content = widget::column::with_capacity(4).push(section).spacing(10.0);
+
+        if let Some(source) = self.spaces.get(self.harmony_source) {
+            let rgb = source.get_rgb();
+            let colors = harmony::generate(rgb, self.harmony, self.harmony_spread);
+
+            let mut preview = widget::row::with_capacity(colors.len() + 1);
+            for (index, [r, g, b, a]) in std::iter::once(rgb)
+                .chain(colors.iter().copied())
+                .enumerate()
+            {
+                let last = index == colors.len();
+                preview = preview.push(
+                    color_block(Color::from_rgba(r, g, b, a))
+                        .border([index == 0, last, last, index == 0])
+                        .height(60.0),
+                );
+            }
+
+            content = content
+                .push(widget::text::body(fl!(
+                    "harmony-source",
+                    column = self.harmony_source + 1
+                )))
+                .push(preview)
+                .push(
+                    widget::button::suggested(fl!("add-harmony", count = colors.len()))
+                        .on_press(Message::AddHarmony),
+                );
+        }
+
+        ContextDrawer {
+            title: Some(fl!("harmony").into()),
+            header_actions: vec![],
+            header: None,
+            content: content.into(),
+            footer: None,
+            on_close: Message::ToggleContextPage(ContextPage::Harmony),
+        }
+    }
+
+    fn scale_page(&self) -> ContextDrawer<Message> {
+        let source = self.scale_source.min(self.spaces.len().saturating_sub(1));
+        let settings = widget::settings::section()
+            .add(widget::settings::item(
+                fl!("scale-source"),
+                widget::dropdown(&self.column_names, Some(source), Message::ChangeScaleSource),
+            ))
+            .add(widget::settings::item(
+                fl!("scale-steps"),
+                widget::dropdown(
+                    &STEP_COUNTS,
+                    Some(self.scale.steps - MIN_STEPS),
+                    Message::ChangeScaleSteps,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("scale-space"),
+                widget::dropdown(
+                    &self.scale_space_names,
+                    ScaleSpace::ALL
+                        .iter()
+                        .position(|space| *space == self.scale.space),
+                    Message::ChangeScaleSpace,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("easing"),
+                widget::dropdown(
+                    &self.easing_names,
+                    Easing::ALL
+                        .iter()
+                        .position(|easing| *easing == self.scale.easing),
+                    Message::ChangeScaleEasing,
+                ),
+            ))
+            .add(
+                widget::settings::item::builder(fl!("vary-chroma"))
+                    .toggler(self.scale.vary_chroma, Message::ToggleScaleChroma),
+            );
+
+        let mut content = widget::column::with_capacity(4)
+            .push(settings)
+            .spacing(10.0);
+
+        if let Some(space) = self.spaces.get(source) {
+            let colors = self.scale.generate(space.get_rgb());
+            let steps = scale::step_names(colors.len());
+
+            let last = colors.len() - 1;
+            let mut strip = widget::row::with_capacity(colors.len());
+            for (index, ([r, g, b, a], step)) in colors.iter().zip(&steps).enumerate() {
+                let color = Color::from_rgba(*r, *g, *b, *a);
+                let color = match self.view_simulation() {
+                    Some(simulation) => simulation.apply_color(color),
+                    None => color,
+                };
+                strip = strip.push(
+                    widget::column::with_capacity(2)
+                        .push(
+                            color_block(color)
+                                .border([index == 0, index == last, index == last, index == 0])
+                                .height(60.0),
+                        )
+                        .push(widget::text::caption(step.clone()))
+                        .align_x(Alignment::Center)
+                        .spacing(2.0)
+                        .width(Length::Fill),
+                );
+            }
+
+            let export = widget::settings::section()
+                .add(widget::settings::item(
+                    fl!("palette-name"),
+                    widget::text_input("", self.scale_name.clone())
+                        .on_input(Message::ChangeScaleName),
+                ))
+                .add(widget::settings::item(
+                    fl!("palette-format"),
+                    widget::dropdown(
+                        &PALETTE_FORMAT_NAMES,
+                        PaletteFormat::ALL
+                            .iter()
+                            .position(|format| *format == self.scale_export),
+                        Message::ChangeScaleExport,
+                    ),
+                ));
+
+            content = content.push(strip).push(export).push(
+                widget::row::with_capacity(2)
+                    .push(
+                        widget::button::standard(fl!("append-columns"))
+                            .on_press(Message::AppendScale),
+                    )
+                    .push(
+                        widget::button::suggested(fl!("export-palette"))
+                            .on_press(Message::ExportScale),
+                    )
+                    .spacing(10.0),
+            );
+        }
+
+        ContextDrawer {
+            title: Some(fl!("scale").into()),
+            header_actions: vec![],
+            header: None,
+            content: content.into(),
+            footer: None,
+            on_close: Message::ToggleContextPage(ContextPage::Scale),
+        }
+    }
+
+    /// The first and last gradient stop, clamped to the columns
+    fn gradient_columns(&self) -> (usize, usize) {
+        let last = self.spaces.len().saturating_sub(1);
+        (self.gradient_first.min(last), self.gradient_last.min(last))
+    }
+
+    /// The colors of the gradient's columns, at most `gradient::MAX_STOPS`
+    fn gradient_stops(&self) -> Vec<[f32; 4]> {
+        let (first, last) = self.gradient_columns();
+        let columns: Vec<usize> = if first <= last {
+            (first..=last).collect()
+        } else {
+            (last..=first).rev().collect()
+        };
+
+        columns
+            .into_iter()
+            .filter_map(|index| self.spaces.get(index))
+            .take(gradient::MAX_STOPS)
+            .map(ColorSpace::get_rgb)
+            .collect()
+    }
+
+    fn gradient_page(&self) -> ContextDrawer<Message> {
+        let (first, last_column) = self.gradient_columns();
+        let mut settings = widget::settings::section()
+            .add(widget::settings::item(
+                fl!("gradient-first"),
+                widget::dropdown(
+                    &self.column_names,
+                    Some(first),
+                    Message::ChangeGradientFirst,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("gradient-last"),
+                widget::dropdown(
+                    &self.column_names,
+                    Some(last_column),
+                    Message::ChangeGradientLast,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("interpolation"),
+                widget::dropdown(
+                    &self.interpolation_names,
+                    Interpolation::ALL
+                        .iter()
+                        .position(|interpolation| *interpolation == self.gradient_interpolation),
+                    Message::ChangeGradientInterpolation,
+                ),
+            ));
+        if self.gradient_interpolation.hue().is_some() {
+            settings = settings.add(widget::settings::item(
+                fl!("hue-interpolation"),
+                widget::dropdown(
+                    &self.hue_method_names,
+                    HueMethod::ALL
+                        .iter()
+                        .position(|hue| *hue == self.gradient_hue),
+                    Message::ChangeGradientHue,
+                ),
+            ));
+        }
+
+        let mut content = widget::column::with_capacity(4)
+            .push(settings)
+            .spacing(10.0);
+
+        let stops = self.gradient_stops();
+        if stops.len() < 2 {
+            content = content.push(widget::text::body(fl!("gradient-needs-stops")));
+        } else {
+            let segments =
+                gradient::segments(&stops, self.gradient_interpolation, self.gradient_hue);
+            let samples = gradient::samples(
+                &stops,
+                self.gradient_interpolation,
+                self.gradient_hue,
+                self.gradient_samples,
+            );
+
+            let last = samples.len() - 1;
+            let mut strip = widget::row::with_capacity(samples.len());
+            for (index, [r, g, b, a]) in samples.into_iter().enumerate() {
+                let color = Color::from_rgba(r, g, b, a);
+                let color = match self.view_simulation() {
+                    Some(simulation) => simulation.apply_color(color),
+                    None => color,
+                };
+                strip = strip.push(
+                    color_block(color)
+                        .border([index == 0, index == last, index == last, index == 0])
+                        .height(24.0),
+                );
+            }
+
+            let columns = first.abs_diff(last_column) + 1;
+            content = content
+                .push(
+                    cosmic::iced_widget::shader(shaders::gradient::ColorGraph {
+                        segments,
+                        interpolation: self.gradient_interpolation,
+                        cvd: self.view_simulation(),
+                    })
+                    .width(Length::Fill)
+                    .height(60),
+                )
+                .push_maybe((stops.len() < columns).then(|| {
+                    widget::text::caption(fl!("gradient-max-stops", count = gradient::MAX_STOPS))
+                }))
+                .push(widget::settings::section().add(widget::settings::item(
+                    fl!("samples"),
+                    widget::dropdown(
+                        &STEP_COUNTS,
+                        Some(self.gradient_samples - MIN_STEPS),
+                        Message::ChangeGradientSamples,
+                    ),
+                )))
+                .push(strip)
+                .push(
+                    widget::row::with_capacity(2)
+                        .push(
+                            widget::button::standard(fl!("copy-samples"))
+                                .on_press(Message::CopyGradientSamples),
+                        )
+                        .push(
+                            widget::button::suggested(fl!("copy-css"))
+                                .on_press(Message::CopyGradientCss),
+                        )
+                        .spacing(10.0),
+                );
+        }
+
+        ContextDrawer {
+            title: Some(fl!("gradient").into()),
+            header_actions: vec![],
+            header: None,
+            content: content.into(),
+            footer: None,
+            on_close: Message::ToggleContextPage(ContextPage::Gradient),
+        }
+    }
+
+    fn copy_formats_page(&self) -> ContextDrawer<Message> {
+        let mut content = widget::column::with_capacity(self.custom_formats.len() + 2)
+            .push(widget::text::body(fl!(
+                "copy-formats-help",
+                examples = "{r8}, {h:.1}, {a%}, {hex}",
+                qualified = "{oklch.h}"
+            )))
+            .spacing(10.0);
+
+        for (index, template) in self.custom_formats.iter().enumerate() {
+            let mut format = widget::column::with_capacity(2).push(
+                widget::row::with_capacity(2)
+                    .push(
+                        widget::text_input(fl!("copy-format-template"), template.clone()).on_input(
+                            move |template| Message::ChangeCopyFormat { index, template },
+                        ),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("user-trash-full-symbolic"))
+                            .on_press(Message::RemoveCopyFormat(index))
+                            .class(theme::Button::Destructive),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(10.0),
+            );
+
+            if let Some(space) = self.spaces.get(self.last_edited) {
+                format = format.push(widget::text::caption(
+                    space.format(template, self.precision),
+                ));
+            }
+
+            content = content.push(format.spacing(5.0));
+        }
+
+        content = content.push(
+            widget::button::standard(fl!("add-copy-format")).on_press(Message::AddCopyFormat),
+        );
+
+        ContextDrawer {
+            title: Some(fl!("copy-formats").into()),
+            header_actions: vec![],
+            header: None,
+            content: content.into(),
+            footer: None,
+            on_close: Message::ToggleContextPage(ContextPage::CopyFormats),
+        }
+    }
+
     fn about(&self) -> ContextDrawer<Message> {
         let repository = "https://github.com/PixelDoted/cosmic-ext-color-picker";
         let hash = env!("VERGEN_GIT_SHA");
@@ -418,7 +1973,78 @@ impl ColorPicker {
             header: None,
             content,
             footer: None,
-            on_close: Message::ToggleAboutPage,
+            on_close: Message::ToggleContextPage(ContextPage::About),
         }
     }
 }
+
+/// APCA lightness contrast of `foreground` over `background` with the
+/// minimum font size of each weight
+fn apca_section<'a>(foreground: [f32; 4], background: [f32; 4]) -> Element<'a, Message> {
+    let lc = apca::lightness_contrast(foreground, background);
+    let polarity = match Polarity::of(lc) {
+        Polarity::DarkOnLight => fl!("dark-on-light"),
+        Polarity::LightOnDark => fl!("light-on-dark"),
+    };
+
+    let mut section = widget::settings::section()
+        .title(fl!("apca"))
+        .add(widget::settings::item(
+            fl!("lightness-contrast"),
+            widget::text::title4(format!("Lc {}", format::format_number(lc, Some(1), 0))),
+        ))
+        .add(widget::settings::item(
+            fl!("polarity"),
+            widget::text::body(polarity),
+        ));
+
+    for (weight, size) in apca::FONT_WEIGHTS.iter().zip(apca::minimum_sizes(lc)) {
+        let size = match size {
+            MinimumSize::Px(size) => fl!("font-size", size = format::format_number(size, None, 2)),
+            MinimumSize::NonText => fl!("non-text-only"),
+            MinimumSize::Unusable => fl!("unusable"),
+        };
+
+        section = section.add(widget::settings::item(
+            fl!("font-weight", weight = *weight),
+            widget::text::body(size),
+        ));
+    }
+
+    section.into()
+}
+
+/// Sample text in `foreground` over `background`, at the normal and large
+/// WCAG text sizes
+fn contrast_preview<'a>(foreground: [f32; 4], background: [f32; 4]) -> Element<'a, Message> {
+    let foreground = Color::from_rgba(foreground[0], foreground[1], foreground[2], foreground[3]);
+    let background = Color::from_rgb(background[0], background[1], background[2]);
+
+    widget::container(
+        widget::column::with_capacity(2)
+            .push(
+                widget::text(fl!("contrast-sample"))
+                    .size(14.0)
+                    .class(theme::Text::Color(foreground)),
+            )
+            .push(
+                widget::text(fl!("contrast-sample"))
+                    .size(24.0)
+                    .class(theme::Text::Color(foreground)),
+            )
+            .spacing(10.0),
+    )
+    .padding(20.0)
+    .width(Length::Fill)
+    .class(theme::Container::custom(move |_| {
+        cosmic::iced_widget::container::Style {
+            background: Some(background.into()),
+            border: cosmic::iced::Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }))
+    .into()
+}
@@ -1,128 +1,730 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+pub mod cie;
 mod cmyk;
+pub mod gamut;
+mod hsl;
 mod hsv;
+mod hwb;
+mod lab;
+mod lch;
 mod oklab;
 mod oklch;
 mod rgb;
+mod xyy;
+mod xyz;
 
-use std::fmt::Display;
+use std::{fmt, ops::RangeInclusive};
 
-pub use cmyk::Cmyk;
-pub use hsv::Hsv;
-pub use oklab::Oklab;
-pub use oklch::Oklch;
-pub use rgb::Rgb;
+use cosmic::{
+    iced::{gradient::ColorStop, Alignment, Color, Length},
+    iced_core::widget::Id,
+    widget,
+};
+use serde::{Deserialize, Serialize};
 
-use crate::fl;
+pub use hsl::hsl_to_rgb;
+pub use hsv::{hsv_to_rgb, rgb_to_hsv};
+pub use hwb::hwb_to_rgb;
+pub use lab::{lab_to_rgb, rgb_to_lab};
+pub use oklab::{oklab_to_linear_rgb, oklab_to_rgb, rgb_to_oklab};
+use oklch::{oklch_to_rgb, rgb_to_oklch};
+
+use crate::{
+    css::{CssColor, CssSpace},
+    cvd::Simulation,
+    fl,
+    format::{self, Placeholder},
+    hex, shaders,
+    wheel::WheelShape,
+    widgets::{alpha_slider, chroma_disc, color_slider, hue_wheel},
+};
+
+/// The stops of a slider gradient, the most an iced gradient holds
+const SLIDER_STOPS: usize = 8;
+/// The alpha of slider colors outside of sRGB, like the graphs
+const OUT_OF_GAMUT_ALPHA: f32 = 0.1;
+/// The chroma at the edge of the OKLCh disc, the end of the OKLCh chroma slider
+const DISC_CHROMA: f32 = 0.37;
+
+/// Every color model, in the order of the color space dropdown.
+pub static MODELS: &[&dyn ColorModel] = &[
+    &rgb::Rgb::SRGB,
+    &rgb::Rgb::SRGB_LINEAR,
+    &rgb::Rgb::DISPLAY_P3,
+    &rgb::Rgb::A98_RGB,
+    &rgb::Rgb::PROPHOTO_RGB,
+    &rgb::Rgb::REC2020,
+    &hsv::Hsv,
+    &hsl::Hsl,
+    &hwb::Hwb,
+    &lab::Lab::D50,
+    &lab::Lab::D65,
+    &lch::Lch::D50,
+    &lch::Lch::D65,
+    &oklab::Oklab,
+    &oklch::Oklch,
+    &xyz::Xyz,
+    &xyy::XyY,
+    &cmyk::Cmyk,
+];
+
+/// Finds a model in [`MODELS`] by its [`ColorModel::id`].
+pub fn model(id: &str) -> Option<&'static dyn ColorModel> {
+    MODELS.iter().copied().find(|model| model.id() == id)
+}
+
+/// A color model, converting its components from and to gamma encoded
+/// (extended) sRGB, the connection space shared by every model.
+///
+/// Alpha isn't a component, it is handled by [`ColorSpace`].
+pub trait ColorModel: Sync {
+    /// Identifies the model in the config and in qualified copy format
+    /// placeholders, e.g. `{oklch.h}`
+    fn id(&self) -> &'static str;
+
+    fn name(&self) -> String;
+
+    fn components(&self) -> &'static [Component];
+
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32>;
+
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3];
+
+    /// The built-in copy formats, see [`crate::format`]
+    fn copy_formats(&self) -> &'static [&'static str];
+
+    /// The RGB space "map into gamut" targets
+    fn gamut(&self) -> cie::RgbSpace {
+        cie::RgbSpace::Srgb
+    }
+
+    /// A graph shown below the slider of `component`, simulating `cvd`
+    fn component_graph<'a>(
+        &self,
+        _component: usize,
+        _values: &[f32],
+        _cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, ColorSpaceMessage>> {
+        None
+    }
+
+    /// A graph shown below every component, simulating `cvd`
+    fn graph<'a>(
+        &self,
+        _values: &[f32],
+        _cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, ColorSpaceMessage>> {
+        None
+    }
+}
+
+impl fmt::Debug for dyn ColorModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+pub struct Component {
+    /// The copy format placeholder, e.g. `h` for `{h}`
+    pub name: &'static str,
+    pub label: fn() -> String,
+    pub range: RangeInclusive<f32>,
+    /// The slider's whole step when snapping, a degree for hues and an
+    /// 8-bit step for RGB
+    pub snap: Option<f32>,
+}
 
 #[derive(Clone, Debug)]
 pub enum ColorSpaceMessage {
-    ChangeValue { index: usize, value: f32 },
-    ChangeString { index: usize, string: String },
+    ChangeValue {
+        index: usize,
+        value: f32,
+    },
+    /// Several components picked at once on a graph
+    ChangeValues {
+        values: Vec<(usize, f32)>,
+    },
+    ChangeString {
+        index: usize,
+        string: String,
+    },
+    /// An sRGB color picked on a wheel, keeping alpha
+    ChangeRgb([f32; 3]),
+    /// An OKLCh color picked on the disc, keeping alpha
+    ChangeOklch([f32; 3]),
 }
 
-#[derive(Clone, Default, Debug)]
-pub enum ColorSpaceCombo {
+/// How a column picks its color
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PickerLayout {
+    /// A slider for every component
     #[default]
-    Rgb,
-    Hsv,
-    Oklab,
-    Oklch,
-    Cmyk,
-}
-
-impl Display for ColorSpaceCombo {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ColorSpaceCombo::Rgb => f.write_str(&fl!("rgb")),
-            ColorSpaceCombo::Hsv => f.write_str(&fl!("hsv")),
-            ColorSpaceCombo::Oklab => f.write_str(&fl!("oklab")),
-            ColorSpaceCombo::Oklch => f.write_str(&fl!("oklch")),
-            ColorSpaceCombo::Cmyk => f.write_str(&fl!("cmyk")),
-        }
-    }
+    Sliders,
+    /// An HSV hue ring around a saturation and value triangle
+    WheelTriangle,
+    /// An HSV hue ring around a saturation and value square
+    WheelSquare,
+    /// An OKLCh chroma and hue disc with a lightness slider
+    Disc,
 }
 
-pub enum ColorSpace {
-    Rgb(Rgb),
-    Hsv(Hsv),
-    Oklab(Oklab),
-    Oklch(Oklch),
-    Cmyk(Cmyk),
+impl PickerLayout {
+    /// In the order of the layout dropdown
+    pub const ALL: [Self; 4] = [
+        Self::Sliders,
+        Self::WheelTriangle,
+        Self::WheelSquare,
+        Self::Disc,
+    ];
+}
+
+/// A color in one of the [`MODELS`]
+#[derive(Clone)]
+pub struct ColorSpace {
+    model: &'static dyn ColorModel,
+    /// The model's components followed by alpha
+    values: Vec<f32>,
+    strings: Vec<String>,
+    layout: PickerLayout,
+    /// The OKLCh last picked on the disc, until the color changes some other
+    /// way. Keeps the chroma and hue of black and grays.
+    picked_oklch: Option<[f32; 3]>,
 }
 
 impl Default for ColorSpace {
     fn default() -> Self {
-        Self::Rgb(Rgb::from_rgb([1.0; 3]))
+        Self::new(&rgb::Rgb::SRGB, [1.0; 4])
     }
 }
 
 impl ColorSpace {
-    pub fn change_value(&mut self, index: usize, value: f32) {
-        match self {
-            ColorSpace::Rgb(rgb) => rgb.change_value(index, value),
-            ColorSpace::Hsv(hsv) => hsv.change_value(index, value),
-            ColorSpace::Oklab(oklab) => oklab.change_value(index, value),
-            ColorSpace::Oklch(oklch) => oklch.change_value(index, value),
-            ColorSpace::Cmyk(cmyk) => cmyk.change_value(index, value),
+    pub fn new(model: &'static dyn ColorModel, rgb: [f32; 4]) -> Self {
+        let mut values = model.from_rgb([rgb[0], rgb[1], rgb[2]]);
+        values.push(rgb[3]);
+        Self::with_values(model, values)
+    }
+
+    fn with_values(model: &'static dyn ColorModel, values: Vec<f32>) -> Self {
+        Self {
+            model,
+            strings: values.iter().map(f32::to_string).collect(),
+            values,
+            layout: PickerLayout::default(),
+            picked_oklch: None,
         }
     }
 
+    pub fn model(&self) -> &'static dyn ColorModel {
+        self.model
+    }
+
+    pub fn layout(&self) -> PickerLayout {
+        self.layout
+    }
+
+    pub fn set_layout(&mut self, layout: PickerLayout) {
+        self.layout = layout;
+    }
+
+    pub fn change_value(&mut self, index: usize, value: f32) {
+        self.picked_oklch = None;
+        self.values[index] = value;
+        self.strings[index] = value.to_string();
+    }
+
     pub fn change_string(&mut self, index: usize, string: String) {
-        match self {
-            ColorSpace::Rgb(rgb) => rgb.change_string(index, string),
-            ColorSpace::Hsv(hsv) => hsv.change_string(index, string),
-            ColorSpace::Oklab(oklab) => oklab.change_string(index, string),
-            ColorSpace::Oklch(oklch) => oklch.change_string(index, string),
-            ColorSpace::Cmyk(cmyk) => cmyk.change_string(index, string),
+        self.picked_oklch = None;
+        if let Ok(value) = string.parse::<f32>() {
+            self.values[index] = value;
         }
+
+        self.strings[index] = string;
     }
 }
 
 impl ColorSpace {
-    pub fn to_rgb(&self) -> ColorSpace {
-        let rgb = self.get_rgb();
-        Self::Rgb(Rgb::from_rgb(rgb))
+    pub fn convert(&self, model: &'static dyn ColorModel) -> ColorSpace {
+        Self {
+            layout: self.layout,
+            ..Self::new(model, self.get_rgb())
+        }
+    }
+
+    pub fn from_rgb(&mut self, rgb: [f32; 4]) {
+        *self = Self {
+            layout: self.layout,
+            ..Self::new(self.model, rgb)
+        };
     }
 
-    pub fn to_hsv(&self) -> ColorSpace {
+    /// Sets the color to an OKLCh color, keeping alpha and remembering `lch`
+    /// for the disc
+    pub fn set_oklch(&mut self, lch: [f32; 3]) {
+        let [r, g, b] = oklch_to_rgb(lch[0], lch[1], lch[2]);
+        self.from_rgb([r, g, b, self.get_rgb()[3]]);
+        self.picked_oklch = Some(lch);
+    }
+
+    /// The OKLCh of the disc, as last picked on it if nothing else changed
+    fn disc_oklch(&self) -> [f32; 3] {
+        self.picked_oklch.unwrap_or_else(|| {
+            let [r, g, b, _] = self.get_rgb();
+            rgb_to_oklch(r, g, b)
+        })
+    }
+
+    /// Loads a parsed CSS color, switching to the matching space when there is one.
+    pub fn from_css(&mut self, color: &CssColor) {
+        let id = match color.space {
+            CssSpace::Srgb => Some("rgb"),
+            CssSpace::Hsl => Some("hsl"),
+            CssSpace::Hwb => Some("hwb"),
+            CssSpace::Lab => Some("lab"),
+            CssSpace::Lch => Some("lch"),
+            CssSpace::Oklab => Some("oklab"),
+            CssSpace::Oklch => Some("oklch"),
+            CssSpace::Rgb(cie::RgbSpace::Srgb) => Some("rgb"),
+            CssSpace::Rgb(space) => Some(space.css_name()),
+            CssSpace::XyzD65 => Some("xyz"),
+            _ => None,
+        };
+
+        match id.and_then(model) {
+            Some(model) => {
+                let mut values = color.components.to_vec();
+                values.push(color.alpha);
+                *self = Self {
+                    layout: self.layout,
+                    ..Self::with_values(model, values)
+                };
+            }
+            None => self.from_rgb(color.to_rgb()),
+        }
+    }
+
+    /// Whether the color is inside of [`ColorModel::gamut`]
+    pub fn in_gamut(&self) -> bool {
         let rgb = self.get_rgb();
-        Self::Hsv(Hsv::from_rgb(rgb))
+        gamut::in_gamut([rgb[0], rgb[1], rgb[2]], self.model.gamut())
     }
 
-    pub fn to_oklab(&self) -> ColorSpace {
+    /// Clamps the color into [`ColorModel::gamut`], see [`gamut::clip`]
+    pub fn clip_into_gamut(&mut self) {
         let rgb = self.get_rgb();
-        Self::Oklab(Oklab::from_rgb(rgb))
+        let [r, g, b] = gamut::clip([rgb[0], rgb[1], rgb[2]], self.model.gamut());
+        self.from_rgb([r, g, b, rgb[3]]);
     }
 
-    pub fn to_oklch(&self) -> ColorSpace {
+    /// Maps the color into [`ColorModel::gamut`], see [`gamut::map_into_gamut`]
+    pub fn map_into_gamut(&mut self) {
         let rgb = self.get_rgb();
-        Self::Oklch(Oklch::from_rgb(rgb))
+        let [r, g, b] = gamut::map_into_gamut([rgb[0], rgb[1], rgb[2]], self.model.gamut());
+        self.from_rgb([r, g, b, rgb[3]]);
+    }
+
+    pub fn get_rgb(&self) -> [f32; 4] {
+        let (alpha, values) = self.values.split_last().unwrap();
+        let [r, g, b] = self.model.to_rgb(values);
+        [r, g, b, *alpha]
+    }
+
+    /// Every component, alpha included, as stored in the config
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// The inverse of [`ColorSpace::values`], `None` if the component count
+    /// doesn't match the model.
+    pub fn from_values(model: &'static dyn ColorModel, values: &[f32]) -> Option<Self> {
+        if model.components().len() + 1 != values.len() {
+            return None;
+        }
+
+        Some(Self::with_values(model, values.to_vec()))
+    }
+}
+
+impl ColorSpace {
+    /// Renders a copy format template for this color, numbers without an
+    /// explicit precision use up to `precision` decimals.
+    pub fn format(&self, template: &str, precision: usize) -> String {
+        format::render(template, precision, |space, name| {
+            let Some(space) = space else {
+                return self.placeholder(name, true);
+            };
+
+            self.convert(model(space)?).placeholder(name, false)
+        })
     }
 
-    pub fn to_cmyk(&self) -> ColorSpace {
+    /// Looks up a component by name, then alpha, then (if `fallback`)
+    /// the sRGB components.
+    fn placeholder(&self, name: &str, fallback: bool) -> Option<Placeholder> {
         let rgb = self.get_rgb();
-        Self::Cmyk(Cmyk::from_rgb(rgb))
+        if name == "hex" {
+            return Some(Placeholder::Text(hex::to_hex(rgb)));
+        }
+
+        let components = self.model.components();
+        if let Some(index) = components.iter().position(|c| c.name == name) {
+            return Some(Placeholder::Number(self.values[index]));
+        }
+
+        let value = match name {
+            "alpha" | "a" => rgb[3],
+            "r" if fallback => rgb[0],
+            "g" if fallback => rgb[1],
+            "b" if fallback => rgb[2],
+            _ => return None,
+        };
+
+        Some(Placeholder::Number(value))
+    }
+}
+
+impl ColorSpace {
+    /// The component cards, or the picker of the [`PickerLayout`], with the
+    /// sliders and graphs as seen with `cvd`. `snap` rounds the sliders to
+    /// their component's whole steps. `column` keeps the slider ids stable.
+    /// The pickers need the shader widget, without it the sliders are shown.
+    pub fn view<'a>(
+        &self,
+        column: usize,
+        show_graphs: bool,
+        cvd: Option<Simulation>,
+        snap: bool,
+    ) -> cosmic::Element<'a, ColorSpaceMessage> {
+        let simulate = |color: Color| match cvd {
+            Some(simulation) => simulation.apply_color(color),
+            None => color,
+        };
+        let components = self.model.components();
+        let mut content = widget::column::with_capacity(components.len() + 2).spacing(10.0);
+        let layout = if shaders::gpu_available() {
+            self.layout
+        } else {
+            PickerLayout::Sliders
+        };
+
+        if layout != PickerLayout::Sliders {
+            content = content.push(
+                widget::container(self.picker(column, cvd)).class(cosmic::style::Container::Card),
+            );
+        }
+
+        let sliders = if layout == PickerLayout::Sliders {
+            components
+        } else {
+            &[]
+        };
+        for (index, component) in sliders.iter().enumerate() {
+            let gradient: Vec<ColorStop> = self
+                .slider_gradient(index)
+                .into_iter()
+                .map(|stop| ColorStop {
+                    color: simulate(stop.color),
+                    ..stop
+                })
+                .collect();
+
+            let mut card = widget::column::with_capacity(3)
+                .push(
+                    widget::row::with_capacity(2)
+                        .push(widget::text((component.label)()).size(20.0))
+                        .push(
+                            widget::text_input("", self.strings[index].clone()).on_input(
+                                move |string| ColorSpaceMessage::ChangeString { index, string },
+                            ),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(10.0),
+                )
+                .push(
+                    color_slider(
+                        slider_id(column, component.name),
+                        component.range.clone(),
+                        self.values[index],
+                        move |value| ColorSpaceMessage::ChangeValue { index, value },
+                        &gradient,
+                    )
+                    .name((component.label)())
+                    .snap(component.snap.filter(|_| snap)),
+                )
+                .spacing(10.0)
+                .padding(10.0);
+
+            if show_graphs {
+                if let Some(graph) = self.model.component_graph(index, &self.values, cvd) {
+                    card = card.push(graph);
+                }
+            }
+
+            content = content.push(widget::container(card).class(cosmic::style::Container::Card));
+        }
+
+        let alpha = components.len();
+        let [r, g, b, _] = self.get_rgb();
+        content = content.push(
+            widget::container(alpha_view(
+                slider_id(column, "alpha"),
+                alpha,
+                self.values[alpha],
+                &self.strings[alpha],
+                simulate(Color::from_rgb(r, g, b)),
+            ))
+            .class(cosmic::style::Container::Card),
+        );
+
+        if show_graphs {
+            if let Some(graph) = self.model.graph(&self.values, cvd) {
+                content = content.push(
+                    widget::container(widget::container(graph).padding(10.0))
+                        .class(cosmic::style::Container::Card),
+                );
+            }
+        }
+
+        content.into()
+    }
+
+    /// The hue wheel or OKLCh disc of a picker layout
+    fn picker<'a>(
+        &self,
+        column: usize,
+        cvd: Option<Simulation>,
+    ) -> cosmic::Element<'a, ColorSpaceMessage> {
+        let [r, g, b, _] = self.get_rgb();
+        let picker = match self.layout {
+            PickerLayout::Disc => {
+                let [lightness, chroma, hue] = self.disc_oklch();
+                let gradient: Vec<ColorStop> =
+                    sweep_gradient(&(0.0..=1.0), |l| oklch_to_rgb(l, chroma, hue))
+                        .into_iter()
+                        .map(|stop| ColorStop {
+                            color: match cvd {
+                                Some(simulation) => simulation.apply_color(stop.color),
+                                None => stop.color,
+                            },
+                            ..stop
+                        })
+                        .collect();
+
+                widget::column::with_capacity(2)
+                    .push(chroma_disc(
+                        [lightness, chroma, hue],
+                        DISC_CHROMA,
+                        cvd,
+                        move |[chroma, hue]| {
+                            ColorSpaceMessage::ChangeOklch([lightness, chroma, hue])
+                        },
+                    ))
+                    .push(
+                        color_slider(
+                            slider_id(column, "disc-lightness"),
+                            0.0..=1.0,
+                            lightness,
+                            move |lightness| {
+                                ColorSpaceMessage::ChangeOklch([lightness, chroma, hue])
+                            },
+                            &gradient,
+                        )
+                        .name(fl!("lightness")),
+                    )
+                    .spacing(10.0)
+                    .align_x(Alignment::Center)
+                    .into()
+            }
+            layout => {
+                let shape = match layout {
+                    PickerLayout::WheelSquare => WheelShape::Square,
+                    _ => WheelShape::Triangle,
+                };
+                // The wheel only holds sRGB
+                let [r, g, b] = [r, g, b].map(|c| c.clamp(0.0, 1.0));
+                hue_wheel(rgb_to_hsv(r, g, b), shape, cvd, |[h, s, v]| {
+                    ColorSpaceMessage::ChangeRgb(hsv_to_rgb(h, s, v))
+                })
+            }
+        };
+
+        widget::container(picker)
+            .center_x(Length::Fill)
+            .padding(10.0)
+            .into()
+    }
+
+    /// The gradient of the slider of `index`, sweeping the component over
+    /// its range while the others keep their current values. Colors outside
+    /// of sRGB are faded.
+    pub fn slider_gradient(&self, index: usize) -> Vec<ColorStop> {
+        let range = &self.model.components()[index].range;
+        let mut values = self.values.clone();
+        values.pop();
+
+        sweep_gradient(range, |value| {
+            values[index] = value;
+            self.model.to_rgb(&values)
+        })
     }
+}
+
+/// [`SLIDER_STOPS`] colors of `to_rgb` over `range`, fading those outside
+/// of sRGB
+fn sweep_gradient(
+    range: &RangeInclusive<f32>,
+    mut to_rgb: impl FnMut(f32) -> [f32; 3],
+) -> Vec<ColorStop> {
+    (0..SLIDER_STOPS)
+        .map(|stop| {
+            let offset = stop as f32 / (SLIDER_STOPS - 1) as f32;
+            let rgb = to_rgb(range.start() + (range.end() - range.start()) * offset);
+            let alpha = if gamut::in_gamut(rgb, cie::RgbSpace::Srgb) {
+                1.0
+            } else {
+                OUT_OF_GAMUT_ALPHA
+            };
+            // `max` first, turning NaN of undefined colors into black
+            let [r, g, b] = rgb.map(|c| c.max(0.0).min(1.0));
+            ColorStop {
+                offset,
+                color: Color::from_rgba(r, g, b, alpha),
+            }
+        })
+        .collect()
+}
+
+/// The id of the slider of `name` in `column`, the same in every view
+fn slider_id(column: usize, name: &str) -> Id {
+    Id::new(format!("slider-{column}-{name}"))
+}
+
+/// The alpha component card shared by every color space view.
+fn alpha_view<'a>(
+    id: Id,
+    index: usize,
+    value: f32,
+    string: &str,
+    color: Color,
+) -> cosmic::Element<'a, ColorSpaceMessage> {
+    widget::column::with_capacity(2)
+        .push(
+            widget::row::with_capacity(2)
+                .push(widget::text(fl!("alpha")).size(20.0))
+                .push(
+                    widget::text_input("", string.to_string())
+                        .on_input(move |string| ColorSpaceMessage::ChangeString { index, string }),
+                )
+                .align_y(Alignment::Center)
+                .spacing(10.0),
+        )
+        .push(
+            alpha_slider(
+                id,
+                0f32..=1f32,
+                value,
+                move |value| ColorSpaceMessage::ChangeValue { index, value },
+                color,
+            )
+            .name(fl!("alpha")),
+        )
+        .spacing(10.0)
+        .padding(10.0)
+        .into()
+}
+
+#[cfg(test)]
+mod test {
+    use cosmic::iced::Color;
+
+    use super::{model, ColorSpace, MODELS, OUT_OF_GAMUT_ALPHA, SLIDER_STOPS};
+    use crate::format::DEFAULT_PRECISION;
+
+    #[test]
+    fn copy_formats() {
+        let mut space = ColorSpace::default();
+        space.from_rgb([1.0, 0.5, 0.0, 0.5]);
 
-    pub fn from_rgb(&mut self, rgb_in: [f32; 3]) {
-        match self {
-            ColorSpace::Rgb(rgb) => *rgb = rgb::Rgb::from_rgb(rgb_in),
-            ColorSpace::Hsv(hsv) => *hsv = hsv::Hsv::from_rgb(rgb_in),
-            ColorSpace::Oklab(oklab) => *oklab = oklab::Oklab::from_rgb(rgb_in),
-            ColorSpace::Oklch(oklch) => *oklch = oklch::Oklch::from_rgb(rgb_in),
-            ColorSpace::Cmyk(cmyk) => *cmyk = cmyk::Cmyk::from_rgb(rgb_in),
+        let format = |space: &ColorSpace, template| space.format(template, DEFAULT_PRECISION);
+        let hsv = space.convert(model("hsv").unwrap());
+        let oklab = space.convert(model("oklab").unwrap());
+        assert_eq!(format(&space, "{r8} {g8} {b8} {a%}%"), "255 128 0 50%");
+        assert_eq!(format(&hsv, "{h:.1} {r8}"), "30.0 255");
+        assert_eq!(format(&oklab, "{rgb.b} {alpha}"), "0 0.5");
+        assert_eq!(format(&space, "{oklch.l:.2}"), "0.73");
+        assert_eq!(format(&space, "{hex}"), "#FF800080");
+        assert_eq!(space.format("{oklch.l}", 1), "0.7");
+    }
+
+    #[test]
+    fn alpha_survives_conversion() {
+        let mut space = ColorSpace::default();
+        space.from_rgb([0.2, 0.4, 0.6, 0.5]);
+
+        for model in MODELS {
+            let alpha = space.convert(*model).get_rgb()[3];
+            assert!((alpha - 0.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let mut space = ColorSpace::default();
+        space.from_rgb([0.2, 0.4, 0.6, 0.5]);
+
+        for model in MODELS {
+            let space = space.convert(*model);
+            let restored = ColorSpace::from_values(*model, space.values()).unwrap();
+            assert_eq!(restored.model().id(), model.id());
+            assert_eq!(restored.values(), space.values());
         }
+
+        assert!(ColorSpace::from_values(model("cmyk").unwrap(), &[0.0; 4]).is_none());
+    }
+
+    #[test]
+    fn disc_keeps_chroma_and_hue() {
+        let mut space = ColorSpace::default();
+        space.set_oklch([0.0, 0.1, 200.0]);
+        assert_eq!(space.disc_oklch(), [0.0, 0.1, 200.0]);
+        assert_eq!(space.get_rgb()[3], 1.0);
+
+        // Until the color changes some other way
+        space.change_value(0, 0.5);
+        assert_ne!(space.disc_oklch()[2], 200.0);
+    }
+
+    #[test]
+    fn slider_gradients() {
+        // The saturation of blue fades from white to blue
+        let hsv = ColorSpace::new(model("hsv").unwrap(), [0.0, 0.0, 1.0, 0.5]);
+        let saturation = hsv.slider_gradient(1);
+        assert_eq!(saturation.len(), SLIDER_STOPS);
+        assert_eq!(saturation[0].color, Color::WHITE);
+        assert_eq!(
+            saturation[SLIDER_STOPS - 1].color,
+            Color::from_rgb(0.0, 0.0, 1.0)
+        );
+
+        // Strong chroma leaves sRGB
+        let oklch = ColorSpace::new(model("oklch").unwrap(), [0.5, 0.5, 0.5, 1.0]);
+        let chroma = oklch.slider_gradient(1);
+        assert_eq!(chroma[0].color.a, 1.0);
+        assert_eq!(chroma[SLIDER_STOPS - 1].color.a, OUT_OF_GAMUT_ALPHA);
     }
 
-    pub fn get_rgb(&self) -> [f32; 3] {
-        match self {
-            ColorSpace::Rgb(rgb) => rgb.to_rgb(),
-            ColorSpace::Hsv(hsv) => hsv.to_rgb(),
-            ColorSpace::Oklab(oklab) => oklab.to_rgb(),
-            ColorSpace::Oklch(oklch) => oklch.to_rgb(),
-            ColorSpace::Cmyk(cmyk) => cmyk.to_rgb(),
+    #[test]
+    fn unique_ids() {
+        for (index, model) in MODELS.iter().enumerate() {
+            assert!(MODELS[index + 1..]
+                .iter()
+                .all(|other| other.id() != model.id()));
+            assert_eq!(
+                model.from_rgb([0.5; 3]).len(),
+                model.components().len(),
+                "{}",
+                model.id()
+            );
         }
     }
 }
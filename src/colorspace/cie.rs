@@ -0,0 +1,481 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+// https://www.w3.org/TR/css-color-4/#color-conversion-code
+#![allow(clippy::excessive_precision)]
+
+pub type Matrix = [[f32; 3]; 3];
+
+/// D50 reference white, as used by CSS and ICC
+pub const D50: [f32; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+/// D65 reference white
+pub const D65: [f32; 3] = [0.3127 / 0.3290, 1.0, (1.0 - 0.3127 - 0.3290) / 0.3290];
+
+const D65_TO_D50: Matrix = [
+    [
+        1.0479297925449969,
+        0.022946870601609652,
+        -0.05019226628920524,
+    ],
+    [
+        0.02962780877005599,
+        0.9904344267538799,
+        -0.017073799063418826,
+    ],
+    [
+        -0.009243040646204504,
+        0.015055191490298152,
+        0.7518742814281371,
+    ],
+];
+const D50_TO_D65: Matrix = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [
+        -0.0283697093338637,
+        1.0099953980813041,
+        0.021041441191917323,
+    ],
+    [
+        0.012314014864481998,
+        -0.020507649298898964,
+        1.330365926242124,
+    ],
+];
+
+const SRGB_TO_XYZ: Matrix = [
+    [506752.0 / 1228815.0, 87881.0 / 245763.0, 12673.0 / 70218.0],
+    [87098.0 / 409605.0, 175762.0 / 245763.0, 12673.0 / 175545.0],
+    [7918.0 / 409605.0, 87881.0 / 737289.0, 1001167.0 / 1053270.0],
+];
+const XYZ_TO_SRGB: Matrix = [
+    [12831.0 / 3959.0, -329.0 / 214.0, -1974.0 / 3959.0],
+    [
+        -851781.0 / 878810.0,
+        1648619.0 / 878810.0,
+        36519.0 / 878810.0,
+    ],
+    [705.0 / 12673.0, -2585.0 / 12673.0, 705.0 / 667.0],
+];
+
+const DISPLAY_P3_TO_XYZ: Matrix = [
+    [
+        608311.0 / 1250200.0,
+        189793.0 / 714400.0,
+        198249.0 / 1000160.0,
+    ],
+    [
+        35783.0 / 156275.0,
+        247089.0 / 357200.0,
+        198249.0 / 2500400.0,
+    ],
+    [0.0, 32229.0 / 714400.0, 5220557.0 / 5000800.0],
+];
+const XYZ_TO_DISPLAY_P3: Matrix = [
+    [
+        446124.0 / 178915.0,
+        -333277.0 / 357830.0,
+        -72051.0 / 178915.0,
+    ],
+    [-14852.0 / 17905.0, 63121.0 / 35810.0, 423.0 / 17905.0],
+    [11844.0 / 330415.0, -50337.0 / 660830.0, 316169.0 / 330415.0],
+];
+
+const A98_RGB_TO_XYZ: Matrix = [
+    [
+        573536.0 / 994567.0,
+        263643.0 / 1420810.0,
+        187206.0 / 994567.0,
+    ],
+    [
+        591459.0 / 1989134.0,
+        6239551.0 / 9945670.0,
+        374412.0 / 4972835.0,
+    ],
+    [
+        53769.0 / 1989134.0,
+        351524.0 / 4972835.0,
+        4929758.0 / 4972835.0,
+    ],
+];
+const XYZ_TO_A98_RGB: Matrix = [
+    [
+        1829569.0 / 896150.0,
+        -506331.0 / 896150.0,
+        -308931.0 / 896150.0,
+    ],
+    [
+        -851781.0 / 878810.0,
+        1648619.0 / 878810.0,
+        36519.0 / 878810.0,
+    ],
+    [
+        16779.0 / 1248040.0,
+        -147721.0 / 1248040.0,
+        1266979.0 / 1248040.0,
+    ],
+];
+
+// Relative to D50
+const PROPHOTO_RGB_TO_XYZ: Matrix = [
+    [
+        0.79776664490064230,
+        0.13518129740053308,
+        0.03134773412839220,
+    ],
+    [
+        0.28807482881940130,
+        0.71183523424187300,
+        0.00008993693872564,
+    ],
+    [0.0, 0.0, 0.82510460251046020],
+];
+const XYZ_TO_PROPHOTO_RGB: Matrix = [
+    [
+        1.34578688164715830,
+        -0.25557208737979464,
+        -0.05110186497554526,
+    ],
+    [
+        -0.54463070512490190,
+        1.50824774284514680,
+        0.02052744743642139,
+    ],
+    [0.0, 0.0, 1.21196754563894520],
+];
+
+const REC2020_TO_XYZ: Matrix = [
+    [
+        63426534.0 / 99577255.0,
+        20160776.0 / 139408157.0,
+        47086771.0 / 278816314.0,
+    ],
+    [
+        26158966.0 / 99577255.0,
+        472592308.0 / 697040785.0,
+        8267143.0 / 139408157.0,
+    ],
+    [0.0, 19567812.0 / 697040785.0, 295819943.0 / 278816314.0],
+];
+const XYZ_TO_REC2020: Matrix = [
+    [
+        30757411.0 / 17917100.0,
+        -6372589.0 / 17917100.0,
+        -4539589.0 / 17917100.0,
+    ],
+    [
+        -19765991.0 / 29648200.0,
+        47925759.0 / 29648200.0,
+        467509.0 / 29648200.0,
+    ],
+    [
+        792561.0 / 44930125.0,
+        -1921689.0 / 44930125.0,
+        42328811.0 / 44930125.0,
+    ],
+];
+
+pub fn mul(m: &Matrix, v: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+/// Bradford chromatic adaptation from D65 to D50
+pub fn d65_to_d50(xyz: [f32; 3]) -> [f32; 3] {
+    mul(&D65_TO_D50, xyz)
+}
+
+/// Bradford chromatic adaptation from D50 to D65
+pub fn d50_to_d65(xyz: [f32; 3]) -> [f32; 3] {
+    mul(&D50_TO_D65, xyz)
+}
+
+/// A reference white for CIE L*a*b*, XYZ is Bradford adapted to it from D65
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Illuminant {
+    D50,
+    D65,
+}
+
+impl Illuminant {
+    pub fn white(self) -> [f32; 3] {
+        match self {
+            Illuminant::D50 => D50,
+            Illuminant::D65 => D65,
+        }
+    }
+
+    /// Adapts XYZ relative to D65 to this white
+    pub fn from_d65(self, xyz: [f32; 3]) -> [f32; 3] {
+        match self {
+            Illuminant::D50 => d65_to_d50(xyz),
+            Illuminant::D65 => xyz,
+        }
+    }
+
+    /// Adapts XYZ relative to this white to D65
+    pub fn to_d65(self, xyz: [f32; 3]) -> [f32; 3] {
+        match self {
+            Illuminant::D50 => d50_to_d65(xyz),
+            Illuminant::D65 => xyz,
+        }
+    }
+}
+
+/// sRGB transfer function, extended to negative values
+pub fn srgb_to_linear(c: f32) -> f32 {
+    let abs = c.abs();
+    if abs <= 0.04045 {
+        c / 12.92
+    } else {
+        c.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Inverse sRGB transfer function, extended to negative values
+pub fn linear_to_srgb(c: f32) -> f32 {
+    let abs = c.abs();
+    if abs <= 0.0031308 {
+        c * 12.92
+    } else {
+        c.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    }
+}
+
+/// The RGB spaces understood by CSS `color()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RgbSpace {
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    A98Rgb,
+    ProphotoRgb,
+    Rec2020,
+}
+
+impl RgbSpace {
+    /// Decodes a single component into linear light
+    pub fn to_linear(self, c: f32) -> f32 {
+        let abs = c.abs();
+        match self {
+            RgbSpace::Srgb | RgbSpace::DisplayP3 => srgb_to_linear(c),
+            RgbSpace::SrgbLinear => c,
+            RgbSpace::A98Rgb => c.signum() * abs.powf(563.0 / 256.0),
+            RgbSpace::ProphotoRgb => {
+                if abs <= 16.0 / 512.0 {
+                    c / 16.0
+                } else {
+                    c.signum() * abs.powf(1.8)
+                }
+            }
+            RgbSpace::Rec2020 => {
+                const ALPHA: f32 = 1.09929682680944;
+                const BETA: f32 = 0.018053968510807;
+
+                if abs < BETA * 4.5 {
+                    c / 4.5
+                } else {
+                    c.signum() * ((abs + ALPHA - 1.0) / ALPHA).powf(1.0 / 0.45)
+                }
+            }
+        }
+    }
+
+    /// Encodes a single linear light component
+    pub fn from_linear(self, c: f32) -> f32 {
+        let abs = c.abs();
+        match self {
+            RgbSpace::Srgb | RgbSpace::DisplayP3 => linear_to_srgb(c),
+            RgbSpace::SrgbLinear => c,
+            RgbSpace::A98Rgb => c.signum() * abs.powf(256.0 / 563.0),
+            RgbSpace::ProphotoRgb => {
+                if abs >= 1.0 / 512.0 {
+                    c.signum() * abs.powf(1.0 / 1.8)
+                } else {
+                    c * 16.0
+                }
+            }
+            RgbSpace::Rec2020 => {
+                const ALPHA: f32 = 1.09929682680944;
+                const BETA: f32 = 0.018053968510807;
+
+                if abs > BETA {
+                    c.signum() * (ALPHA * abs.powf(0.45) - (ALPHA - 1.0))
+                } else {
+                    c * 4.5
+                }
+            }
+        }
+    }
+
+    /// Converts encoded RGB to XYZ relative to D65
+    pub fn to_xyz(self, rgb: [f32; 3]) -> [f32; 3] {
+        let linear = rgb.map(|c| self.to_linear(c));
+        match self {
+            RgbSpace::Srgb | RgbSpace::SrgbLinear => mul(&SRGB_TO_XYZ, linear),
+            RgbSpace::DisplayP3 => mul(&DISPLAY_P3_TO_XYZ, linear),
+            RgbSpace::A98Rgb => mul(&A98_RGB_TO_XYZ, linear),
+            RgbSpace::ProphotoRgb => d50_to_d65(mul(&PROPHOTO_RGB_TO_XYZ, linear)),
+            RgbSpace::Rec2020 => mul(&REC2020_TO_XYZ, linear),
+        }
+    }
+
+    /// Converts XYZ relative to D65 to encoded RGB
+    pub fn from_xyz(self, xyz: [f32; 3]) -> [f32; 3] {
+        let linear = match self {
+            RgbSpace::Srgb | RgbSpace::SrgbLinear => mul(&XYZ_TO_SRGB, xyz),
+            RgbSpace::DisplayP3 => mul(&XYZ_TO_DISPLAY_P3, xyz),
+            RgbSpace::A98Rgb => mul(&XYZ_TO_A98_RGB, xyz),
+            RgbSpace::ProphotoRgb => mul(&XYZ_TO_PROPHOTO_RGB, d65_to_d50(xyz)),
+            RgbSpace::Rec2020 => mul(&XYZ_TO_REC2020, xyz),
+        };
+
+        linear.map(|c| self.from_linear(c))
+    }
+}
+
+impl RgbSpace {
+    /// The `color()` name of the space
+    pub fn css_name(self) -> &'static str {
+        match self {
+            RgbSpace::Srgb => "srgb",
+            RgbSpace::SrgbLinear => "srgb-linear",
+            RgbSpace::DisplayP3 => "display-p3",
+            RgbSpace::A98Rgb => "a98-rgb",
+            RgbSpace::ProphotoRgb => "prophoto-rgb",
+            RgbSpace::Rec2020 => "rec2020",
+        }
+    }
+
+    /// Converts encoded RGB to sRGB, only the transfer function is applied
+    /// when the primaries are the same.
+    pub fn to_srgb(self, rgb: [f32; 3]) -> [f32; 3] {
+        match self {
+            RgbSpace::Srgb => rgb,
+            RgbSpace::SrgbLinear => rgb.map(linear_to_srgb),
+            _ => xyz_to_srgb(self.to_xyz(rgb)),
+        }
+    }
+
+    /// The inverse of [`RgbSpace::to_srgb`]
+    pub fn from_srgb(self, rgb: [f32; 3]) -> [f32; 3] {
+        match self {
+            RgbSpace::Srgb => rgb,
+            RgbSpace::SrgbLinear => rgb.map(srgb_to_linear),
+            _ => self.from_xyz(srgb_to_xyz(rgb)),
+        }
+    }
+}
+
+/// Converts sRGB to XYZ relative to D65
+pub fn srgb_to_xyz(rgb: [f32; 3]) -> [f32; 3] {
+    RgbSpace::Srgb.to_xyz(rgb)
+}
+
+/// Converts XYZ relative to D65 to sRGB
+pub fn xyz_to_srgb(xyz: [f32; 3]) -> [f32; 3] {
+    RgbSpace::Srgb.from_xyz(xyz)
+}
+
+const KAPPA: f32 = 24389.0 / 27.0;
+const EPSILON: f32 = 216.0 / 24389.0;
+
+/// Converts CIE L*a*b* to XYZ relative to `white`
+pub fn lab_to_xyz(lab: [f32; 3], white: [f32; 3]) -> [f32; 3] {
+    let f1 = (lab[0] + 16.0) / 116.0;
+    let f0 = lab[1] / 500.0 + f1;
+    let f2 = f1 - lab[2] / 200.0;
+
+    let x = if f0.powi(3) > EPSILON {
+        f0.powi(3)
+    } else {
+        (116.0 * f0 - 16.0) / KAPPA
+    };
+    let y = if lab[0] > KAPPA * EPSILON {
+        f1.powi(3)
+    } else {
+        lab[0] / KAPPA
+    };
+    let z = if f2.powi(3) > EPSILON {
+        f2.powi(3)
+    } else {
+        (116.0 * f2 - 16.0) / KAPPA
+    };
+
+    [x * white[0], y * white[1], z * white[2]]
+}
+
+/// Converts XYZ relative to `white` to CIE L*a*b*
+pub fn xyz_to_lab(xyz: [f32; 3], white: [f32; 3]) -> [f32; 3] {
+    let f = |v: f32| {
+        if v > EPSILON {
+            v.cbrt()
+        } else {
+            (KAPPA * v + 16.0) / 116.0
+        }
+    };
+    let f0 = f(xyz[0] / white[0]);
+    let f1 = f(xyz[1] / white[1]);
+    let f2 = f(xyz[2] / white[2]);
+
+    [116.0 * f1 - 16.0, 500.0 * (f0 - f1), 200.0 * (f1 - f2)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{d50_to_d65, lab_to_xyz, srgb_to_xyz, xyz_to_lab, xyz_to_srgb, RgbSpace, D50};
+
+    #[test]
+    fn white() {
+        let xyz = srgb_to_xyz([1.0; 3]);
+        assert!(aprox_eq(&xyz, &[0.9504559, 1.0, 1.0890578]));
+
+        let lab = xyz_to_lab(super::d65_to_d50(xyz), D50);
+        assert!(aprox_eq(&lab, &[100.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn red_lab() {
+        // https://www.w3.org/TR/css-color-4/#specifying-lab-lch
+        let xyz = super::d65_to_d50(srgb_to_xyz([1.0, 0.0, 0.0]));
+        let lab = xyz_to_lab(xyz, D50);
+        assert!(aprox_eq_eps(&lab, &[54.29, 80.80, 69.89], 1e-2));
+
+        let rgb = xyz_to_srgb(d50_to_d65(lab_to_xyz(lab, D50)));
+        assert!(aprox_eq(&rgb, &[1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn display_p3() {
+        // sRGB red as display-p3
+        let p3 = RgbSpace::DisplayP3.from_xyz(srgb_to_xyz([1.0, 0.0, 0.0]));
+        assert!(aprox_eq_eps(&p3, &[0.9175, 0.2003, 0.1386], 1e-3));
+    }
+
+    #[test]
+    fn round_trip() {
+        let rgb = [0.2, 0.4, 0.6];
+        for space in [
+            RgbSpace::Srgb,
+            RgbSpace::SrgbLinear,
+            RgbSpace::DisplayP3,
+            RgbSpace::A98Rgb,
+            RgbSpace::ProphotoRgb,
+            RgbSpace::Rec2020,
+        ] {
+            let xyz = space.to_xyz(rgb);
+            assert!(aprox_eq(&space.from_xyz(xyz), &rgb), "{space:?}");
+        }
+    }
+
+    fn aprox_eq(a: &[f32; 3], b: &[f32; 3]) -> bool {
+        aprox_eq_eps(a, b, 1e-4)
+    }
+
+    fn aprox_eq_eps(a: &[f32; 3], b: &[f32; 3], epsilon: f32) -> bool {
+        a.iter()
+            .zip(b)
+            .all(|(a, b)| *a > *b - epsilon && *a < *b + epsilon)
+    }
+}
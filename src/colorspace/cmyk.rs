@@ -1,203 +1,70 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use cosmic::{
-    iced::{gradient::ColorStop, Alignment, Color},
-    widget,
+use crate::{
+    colorspace::{ColorModel, Component},
+    fl,
 };
 
-use crate::{colorspace::ColorSpaceMessage as Message, fl, widgets::color_slider};
-
-const COLOR_STOPS_CYAN: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(1.0, 1.0, 1.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(0.0, 1.0, 1.0),
-    },
-];
-const COLOR_STOPS_MAGENTA: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(1.0, 1.0, 1.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 0.0, 1.0),
-    },
+const COPY_FORMATS: [&str; 5] = [
+    "{c}, {m}, {y}, {k}, {a}",
+    "{hex}",
+    "device-cmyk({c%}% {m%}% {y%}% {k%}% / {a})",
+    "{c%}%, {m%}%, {y%}%, {k%}%",
+    "rgb({r8} {g8} {b8} / {a})",
 ];
-const COLOR_STOPS_YELLOW: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(1.0, 1.0, 1.0),
+
+static COMPONENTS: [Component; 4] = [
+    Component {
+        name: "c",
+        label: || fl!("cyan"),
+        range: 0.0..=1.0,
+        snap: None,
     },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 1.0, 0.0),
+    Component {
+        name: "m",
+        label: || fl!("magenta"),
+        range: 0.0..=1.0,
+        snap: None,
     },
-];
-const COLOR_STOPS_BLACK: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(1.0, 1.0, 1.0),
+    Component {
+        name: "y",
+        label: || fl!("yellow"),
+        range: 0.0..=1.0,
+        snap: None,
     },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
+    Component {
+        name: "k",
+        label: || fl!("black"),
+        range: 0.0..=1.0,
+        snap: None,
     },
 ];
 
-#[derive(Clone)]
-pub struct Cmyk {
-    pub values: [f32; 4],
-    pub strings: [String; 4],
-}
+pub struct Cmyk;
 
-impl Default for Cmyk {
-    fn default() -> Self {
-        Self {
-            values: [0.0; 4],
-            strings: ["0".into(), "0".into(), "0".into(), "0".into()],
-        }
+impl ColorModel for Cmyk {
+    fn id(&self) -> &'static str {
+        "cmyk"
     }
-}
 
-impl Cmyk {
-    pub fn from_rgb(rgb: [f32; 3]) -> Self {
-        let cmyk = rgb_to_cmyk(rgb[0], rgb[1], rgb[2]);
-
-        Self {
-            strings: [
-                cmyk[0].to_string(),
-                cmyk[1].to_string(),
-                cmyk[2].to_string(),
-                cmyk[3].to_string(),
-            ],
-            values: cmyk,
-        }
+    fn name(&self) -> String {
+        fl!("cmyk")
     }
 
-    pub fn to_rgb(&self) -> [f32; 3] {
-        cmyk_to_rgb(
-            self.values[0],
-            self.values[1],
-            self.values[2],
-            self.values[3],
-        )
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
     }
 
-    pub fn copy_to_clipboard(&self) -> String {
-        format!(
-            "{}, {}, {}, {}",
-            self.values[0], self.values[1], self.values[2], self.values[3]
-        )
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        rgb_to_cmyk(rgb[0], rgb[1], rgb[2]).to_vec()
     }
-}
 
-impl Cmyk {
-    pub fn change_value(&mut self, index: usize, value: f32) {
-        self.values[index] = value;
-        self.strings[index] = value.to_string();
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        cmyk_to_rgb(values[0], values[1], values[2], values[3])
     }
 
-    pub fn change_string(&mut self, index: usize, string: String) {
-        if let Ok(value) = string.parse::<f32>() {
-            self.values[index] = value;
-        }
-
-        self.strings[index] = string;
-    }
-
-    pub fn view<'a>(&self, _show_graphs: bool) -> cosmic::Element<'a, Message> {
-        let values = &self.values;
-        let strings = &self.strings;
-
-        let cyan = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("cyan")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[0].clone())
-                            .on_input(|string| Message::ChangeString { index: 0, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[0],
-                |value| Message::ChangeValue { index: 0, value },
-                &COLOR_STOPS_CYAN,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let magenta = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("magenta")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[1].clone())
-                            .on_input(|string| Message::ChangeString { index: 1, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[1],
-                |value| Message::ChangeValue { index: 1, value },
-                &COLOR_STOPS_MAGENTA,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let yellow = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("yellow")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[2].clone())
-                            .on_input(|string| Message::ChangeString { index: 2, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[2],
-                |value| Message::ChangeValue { index: 2, value },
-                &COLOR_STOPS_YELLOW,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let black = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("black")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[3].clone())
-                            .on_input(|string| Message::ChangeString { index: 3, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[3],
-                |value| Message::ChangeValue { index: 3, value },
-                &COLOR_STOPS_BLACK,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-
-        let content = widget::column::with_capacity(3)
-            .push(widget::container(cyan).class(cosmic::style::Container::Card))
-            .push(widget::container(magenta).class(cosmic::style::Container::Card))
-            .push(widget::container(yellow).class(cosmic::style::Container::Card))
-            .push(widget::container(black).class(cosmic::style::Container::Card))
-            .spacing(10.0);
-
-        content.into()
+    fn copy_formats(&self) -> &'static [&'static str] {
+        &COPY_FORMATS
     }
 }
 
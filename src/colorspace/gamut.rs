@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! CSS Color 4 gamut mapping, reducing OKLCh chroma until clipping the
+//! color is no longer noticeable.
+//! https://www.w3.org/TR/css-color-4/#binsearch
+
+use super::{
+    cie::RgbSpace,
+    oklab::{oklab_to_rgb, rgb_to_oklab},
+};
+use crate::delta_e::delta_eok;
+
+/// Just noticeable difference in deltaEOK
+const JND: f32 = 0.02;
+/// Chroma precision of the binary search
+const EPSILON: f32 = 0.0001;
+/// Allowance for rounding errors of the space conversions
+const TOLERANCE: f32 = 1e-5;
+
+/// Whether (extended) sRGB `rgb` is inside of `space`
+pub fn in_gamut(rgb: [f32; 3], space: RgbSpace) -> bool {
+    space
+        .from_srgb(rgb)
+        .iter()
+        .all(|c| (-TOLERANCE..=1.0 + TOLERANCE).contains(c))
+}
+
+/// The smallest of the common RGB gamuts a color is inside of
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gamut {
+    Srgb,
+    DisplayP3,
+    Rec2020,
+    /// Outside of every checked gamut, Rec. 2020 being the largest
+    Out,
+}
+
+impl Gamut {
+    pub fn of(rgb: [f32; 3]) -> Self {
+        if in_gamut(rgb, RgbSpace::Srgb) {
+            Gamut::Srgb
+        } else if in_gamut(rgb, RgbSpace::DisplayP3) {
+            Gamut::DisplayP3
+        } else if in_gamut(rgb, RgbSpace::Rec2020) {
+            Gamut::Rec2020
+        } else {
+            Gamut::Out
+        }
+    }
+}
+
+/// Clamps the components of `rgb` in `space`
+pub fn clip(rgb: [f32; 3], space: RgbSpace) -> [f32; 3] {
+    space.to_srgb(space.from_srgb(rgb).map(|c| c.clamp(0.0, 1.0)))
+}
+
+/// Maps (extended) sRGB `rgb` into `space`, keeping its OKLCh lightness and
+/// hue. The result is sRGB.
+pub fn map_into_gamut(rgb: [f32; 3], space: RgbSpace) -> [f32; 3] {
+    let origin = rgb_to_oklab(rgb[0], rgb[1], rgb[2]);
+    let [l, a, b] = origin;
+    if l >= 1.0 {
+        return [1.0; 3];
+    } else if l <= 0.0 {
+        return [0.0; 3];
+    }
+
+    if in_gamut(rgb, space) {
+        return rgb;
+    }
+
+    let chroma = a.hypot(b);
+    let mut clipped = clip(rgb, space);
+    if chroma <= EPSILON || delta_eok(rgb_to_oklab_array(clipped), origin) < JND {
+        return clipped;
+    }
+
+    let (mut min, mut max) = (0.0, chroma);
+    let mut min_in_gamut = true;
+    while max - min > EPSILON {
+        let c = (min + max) / 2.0;
+        let current = [l, a * c / chroma, b * c / chroma];
+        let current_rgb = oklab_to_rgb(current[0], current[1], current[2]);
+
+        if min_in_gamut && in_gamut(current_rgb, space) {
+            min = c;
+            continue;
+        }
+
+        clipped = clip(current_rgb, space);
+        let e = delta_eok(rgb_to_oklab_array(clipped), current);
+        if e < JND {
+            if JND - e < EPSILON {
+                return clipped;
+            }
+
+            min_in_gamut = false;
+            min = c;
+        } else {
+            max = c;
+        }
+    }
+
+    clipped
+}
+
+fn rgb_to_oklab_array(rgb: [f32; 3]) -> [f32; 3] {
+    rgb_to_oklab(rgb[0], rgb[1], rgb[2])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{in_gamut, map_into_gamut, rgb_to_oklab_array, Gamut, JND};
+    use crate::{
+        colorspace::{cie::RgbSpace, oklab_to_rgb},
+        delta_e::delta_eok,
+    };
+
+    #[test]
+    fn in_gamut_unchanged() {
+        let rgb = [0.2, 0.4, 0.6];
+        assert_eq!(map_into_gamut(rgb, RgbSpace::Srgb), rgb);
+
+        // Display P3 green is outside of sRGB, but inside of Rec. 2020
+        let green = RgbSpace::DisplayP3.to_srgb([0.0, 1.0, 0.0]);
+        assert!(!in_gamut(green, RgbSpace::Srgb));
+        assert_eq!(map_into_gamut(green, RgbSpace::Rec2020), green);
+    }
+
+    #[test]
+    fn smallest_gamut() {
+        assert_eq!(Gamut::of([1.0, 0.0, 0.5]), Gamut::Srgb);
+
+        let p3 = RgbSpace::DisplayP3.to_srgb([0.0, 1.0, 0.0]);
+        assert_eq!(Gamut::of(p3), Gamut::DisplayP3);
+
+        let rec2020 = RgbSpace::Rec2020.to_srgb([0.0, 1.0, 0.0]);
+        assert_eq!(Gamut::of(rec2020), Gamut::Rec2020);
+
+        let prophoto = RgbSpace::ProphotoRgb.to_srgb([0.0, 0.0, 1.0]);
+        assert_eq!(Gamut::of(prophoto), Gamut::Out);
+    }
+
+    #[test]
+    fn lightness_bounds() {
+        assert_eq!(map_into_gamut([1.5, 1.2, 1.1], RgbSpace::Srgb), [1.0; 3]);
+        assert_eq!(map_into_gamut([-0.2, -0.1, -0.1], RgbSpace::Srgb), [0.0; 3]);
+    }
+
+    #[test]
+    fn keeps_lightness_and_hue() {
+        // oklch(0.7 0.3 150), far outside of sRGB
+        let h = 150f32.to_radians();
+        let origin = [0.7, 0.3 * h.cos(), 0.3 * h.sin()];
+        let rgb = oklab_to_rgb(origin[0], origin[1], origin[2]);
+        assert!(!in_gamut(rgb, RgbSpace::Srgb));
+
+        let mapped = map_into_gamut(rgb, RgbSpace::Srgb);
+        assert!(in_gamut(mapped, RgbSpace::Srgb));
+
+        // Within a JND of the original lightness and hue at reduced chroma
+        let mapped = rgb_to_oklab_array(mapped);
+        let c = mapped[1].hypot(mapped[2]);
+        assert!(c < 0.3);
+        assert!(delta_eok(mapped, [0.7, c * h.cos(), c * h.sin()]) < JND);
+    }
+
+    #[test]
+    fn unnoticeable_clipping() {
+        let rgb = [1.001, 0.5, 0.2];
+        let mapped = map_into_gamut(rgb, RgbSpace::Srgb);
+        assert!(in_gamut(mapped, RgbSpace::Srgb));
+        assert!(delta_eok(rgb_to_oklab_array(mapped), rgb_to_oklab_array(rgb)) < JND);
+    }
+}
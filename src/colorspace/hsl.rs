@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::{
+    colorspace::{ColorModel, ColorSpaceMessage as Message, Component},
+    cvd::Simulation,
+    fl,
+    shaders::{self, hsl as shader},
+};
+
+const COPY_FORMATS: [&str; 4] = [
+    "{h}, {s}, {l}, {a}",
+    "{hex}",
+    "hsl({h} {s%}% {l%}% / {a})",
+    "rgb({r8} {g8} {b8} / {a})",
+];
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "h",
+        label: || fl!("hue"),
+        range: 0.0..=360.0,
+        snap: Some(1.0),
+    },
+    Component {
+        name: "s",
+        label: || fl!("saturation"),
+        range: 0.0..=1.0,
+        snap: None,
+    },
+    Component {
+        name: "l",
+        label: || fl!("lightness"),
+        range: 0.0..=1.0,
+        snap: None,
+    },
+];
+
+pub struct Hsl;
+
+impl ColorModel for Hsl {
+    fn id(&self) -> &'static str {
+        "hsl"
+    }
+
+    fn name(&self) -> String {
+        fl!("hsl")
+    }
+
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
+    }
+
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        rgb_to_hsl(rgb[0], rgb[1], rgb[2]).to_vec()
+    }
+
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        hsl_to_rgb(values[0], values[1], values[2])
+    }
+
+    fn copy_formats(&self) -> &'static [&'static str] {
+        &COPY_FORMATS
+    }
+
+    fn graph<'a>(
+        &self,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        if !shaders::gpu_available() {
+            return None;
+        }
+
+        Some(
+            cosmic::iced_widget::shader(shader::ColorGraph {
+                hue: values[0],
+                saturation: values[1],
+                lightness: values[2],
+                cvd,
+            })
+            .width(100)
+            .height(100)
+            .into(),
+        )
+    }
+}
+
+// https://www.w3.org/TR/css-color-4/#hsl-to-rgb
+pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> [f32; 3] {
+    let f = |n: f32| {
+        let k = (n + h / 30.0).rem_euclid(12.0);
+        let a = s * l.min(1.0 - l);
+        l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+
+    [f(0.0), f(8.0), f(4.0)]
+}
+
+// https://www.w3.org/TR/css-color-4/#rgb-to-hsl
+pub fn rgb_to_hsl(r: f32, g: f32, b: f32) -> [f32; 3] {
+    let x_max = r.max(g).max(b);
+    let x_min = r.min(g).min(b);
+    let l = (x_max + x_min) / 2.0;
+
+    let s = if l <= 0.0 || l >= 1.0 {
+        0.0
+    } else {
+        (x_max - l) / l.min(1.0 - l)
+    };
+
+    // HSL shares its hue with HSV
+    let h = super::hsv::rgb_to_hsv(r, g, b)[0];
+    [h, s, l]
+}
+
+// ---- Tests ----
+#[cfg(test)]
+mod test {
+    use super::{hsl_to_rgb, rgb_to_hsl};
+
+    #[test]
+    fn white() {
+        let rgb = [1f32; 3];
+        let hsl = rgb_to_hsl(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hsl, &[0.0, 0.0, 1.0]));
+
+        let rgb = hsl_to_rgb(hsl[0], hsl[1], hsl[2]);
+        assert!(aprox_eq(&rgb, &[1f32; 3]));
+    }
+
+    #[test]
+    fn black() {
+        let rgb = [0f32; 3];
+        let hsl = rgb_to_hsl(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hsl, &[0.0, 0.0, 0.0]));
+
+        let rgb = hsl_to_rgb(hsl[0], hsl[1], hsl[2]);
+        assert!(aprox_eq(&rgb, &[0f32; 3]));
+    }
+
+    #[test]
+    fn red() {
+        let rgb = [1f32, 0f32, 0f32];
+        let hsl = rgb_to_hsl(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hsl, &[0.0, 1.0, 0.5]));
+
+        let rgb = hsl_to_rgb(hsl[0], hsl[1], hsl[2]);
+        assert!(aprox_eq(&rgb, &[1f32, 0f32, 0f32]));
+    }
+
+    #[test]
+    fn green() {
+        let rgb = [0f32, 1f32, 0f32];
+        let hsl = rgb_to_hsl(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hsl, &[120.0, 1.0, 0.5]));
+
+        let rgb = hsl_to_rgb(hsl[0], hsl[1], hsl[2]);
+        assert!(aprox_eq(&rgb, &[0.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn blue() {
+        let rgb = [0f32, 0f32, 1f32];
+        let hsl = rgb_to_hsl(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hsl, &[240.0, 1.0, 0.5]));
+
+        let rgb = hsl_to_rgb(hsl[0], hsl[1], hsl[2]);
+        assert!(aprox_eq(&rgb, &[0f32, 0f32, 1f32]));
+    }
+
+    #[test]
+    fn steel_blue() {
+        // hsl(207.27 44% 49%), steelblue
+        let rgb = [70.0 / 255.0, 130.0 / 255.0, 180.0 / 255.0];
+        let hsl = rgb_to_hsl(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hsl, &[207.27272, 0.44, 0.49019608]));
+
+        let back = hsl_to_rgb(hsl[0], hsl[1], hsl[2]);
+        assert!(aprox_eq(&back, &rgb));
+    }
+
+    fn aprox_eq(a: &[f32; 3], b: &[f32; 3]) -> bool {
+        const EPSILON: f32 = 1e-4;
+
+        a[0] > b[0] - EPSILON
+            && a[0] < b[0] + EPSILON
+            && a[1] > b[1] - EPSILON
+            && a[1] < b[1] + EPSILON
+            && a[2] > b[2] - EPSILON
+            && a[2] < b[2] + EPSILON
+    }
+}
@@ -1,195 +1,100 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use cosmic::{
-    iced::{gradient::ColorStop, Alignment, Color},
-    widget,
-};
-
 use crate::{
-    colorspace::ColorSpaceMessage as Message, fl, shaders::hsv as shader, widgets::color_slider,
+    colorspace::{ColorModel, ColorSpaceMessage as Message, Component},
+    cvd::Simulation,
+    fl,
+    shaders::{
+        self, hsv as shader,
+        raster::{self, Plane},
+    },
 };
 
-const COLOR_STOPS_HUE: [ColorStop; 7] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(1.0, 0.0, 0.0),
-    },
-    ColorStop {
-        offset: 0.166,
-        color: Color::from_rgb(1.0, 1.0, 0.0),
-    },
-    ColorStop {
-        offset: 0.333,
-        color: Color::from_rgb(0.0, 1.0, 0.0),
-    },
-    ColorStop {
-        offset: 0.5,
-        color: Color::from_rgb(0.0, 1.0, 1.0),
-    },
-    ColorStop {
-        offset: 0.666,
-        color: Color::from_rgb(0.0, 0.0, 1.0),
-    },
-    ColorStop {
-        offset: 0.833,
-        color: Color::from_rgb(1.0, 0.0, 1.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 0.0, 0.0),
-    },
+const COPY_FORMATS: [&str; 4] = [
+    "{h}, {s}, {v}, {a}",
+    "{hex}",
+    "{h:.0}°, {s%:.0}%, {v%:.0}%",
+    "rgb({r8} {g8} {b8} / {a})",
 ];
-const COLOR_STOPS_SATURATION: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 0.0, 0.0),
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "h",
+        label: || fl!("hue"),
+        range: 0.0..=360.0,
+        snap: Some(1.0),
     },
-];
-const COLOR_STOPS_VALUE: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
+    Component {
+        name: "s",
+        label: || fl!("saturation"),
+        range: 0.0..=1.0,
+        snap: None,
     },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 1.0, 1.0),
+    Component {
+        name: "v",
+        label: || fl!("value"),
+        range: 0.0..=1.0,
+        snap: None,
     },
 ];
 
-#[derive(Clone)]
-pub struct Hsv {
-    pub values: [f32; 3],
-    pub strings: [String; 3],
-}
-
-impl Hsv {
-    pub fn from_rgb(rgb: [f32; 3]) -> Self {
-        let hsv = rgb_to_hsv(rgb[0], rgb[1], rgb[2]);
+pub struct Hsv;
 
-        Self {
-            strings: [hsv[0].to_string(), hsv[1].to_string(), hsv[2].to_string()],
-            values: hsv,
-        }
+impl ColorModel for Hsv {
+    fn id(&self) -> &'static str {
+        "hsv"
     }
 
-    pub fn to_rgb(&self) -> [f32; 3] {
-        hsv_to_rgb(self.values[0], self.values[1], self.values[2])
+    fn name(&self) -> String {
+        fl!("hsv")
     }
 
-    pub fn copy_to_clipboard(&self) -> String {
-        format!("{}, {}, {}", self.values[0], self.values[1], self.values[2])
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
     }
-}
 
-impl Hsv {
-    pub fn change_value(&mut self, index: usize, value: f32) {
-        self.values[index] = value;
-        self.strings[index] = value.to_string();
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        rgb_to_hsv(rgb[0], rgb[1], rgb[2]).to_vec()
     }
 
-    pub fn change_string(&mut self, index: usize, string: String) {
-        if let Ok(value) = string.parse::<f32>() {
-            self.values[index] = value;
-        }
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        hsv_to_rgb(values[0], values[1], values[2])
+    }
 
-        self.strings[index] = string;
+    fn copy_formats(&self) -> &'static [&'static str] {
+        &COPY_FORMATS
     }
 
-    pub fn view<'a>(&self, show_graphs: bool) -> cosmic::Element<'a, Message> {
-        let values = &self.values;
-        let strings = &self.strings;
-
-        let red = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("hue")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[0].clone())
-                            .on_input(|string| Message::ChangeString { index: 0, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=360f32,
-                values[0],
-                |value| Message::ChangeValue { index: 0, value },
-                &COLOR_STOPS_HUE,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let green = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("saturation")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[1].clone())
-                            .on_input(|string| Message::ChangeString { index: 1, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[1],
-                |value| Message::ChangeValue { index: 1, value },
-                &COLOR_STOPS_SATURATION,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let blue = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("value")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[2].clone())
-                            .on_input(|string| Message::ChangeString { index: 2, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[2],
-                |value| Message::ChangeValue { index: 2, value },
-                &COLOR_STOPS_VALUE,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-
-        let mut content = widget::column::with_capacity(3)
-            .push(widget::container(red).class(cosmic::style::Container::Card))
-            .push(widget::container(green).class(cosmic::style::Container::Card))
-            .push(widget::container(blue).class(cosmic::style::Container::Card))
-            .spacing(10.0);
-
-        if show_graphs {
-            content = content.push(
-                widget::container(
-                    widget::container(
-                        cosmic::iced_widget::shader(shader::ColorGraph {
-                            hue: self.values[0],
-                            saturation: self.values[1],
-                            value: self.values[2],
-                        })
-                        .width(100)
-                        .height(100),
-                    )
-                    .padding(10.0),
-                )
-                .class(cosmic::style::Container::Card),
-            );
+    fn graph<'a>(
+        &self,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        let (hue, saturation, value) = (values[0], values[1], values[2]);
+        if !shaders::gpu_available() {
+            let plane = Plane::Hsv {
+                hue,
+                saturation,
+                value,
+            };
+            return Some(raster::graph(plane, cvd, 100, 100));
         }
 
-        content.into()
+        Some(
+            cosmic::iced_widget::shader(shader::ColorGraph {
+                hue,
+                saturation,
+                value,
+                cvd,
+            })
+            .width(100)
+            .height(100)
+            .into(),
+        )
     }
 }
 
-fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
+pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
     let c = v * s;
     let h_ = h / 60.0;
     let x = c * (1.0 - (h_ % 2.0 - 1.0).abs());
@@ -228,7 +133,7 @@ fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
     [r1 + m, g1 + m, b1 + m]
 }
 
-fn rgb_to_hsv(r: f32, g: f32, b: f32) -> [f32; 3] {
+pub fn rgb_to_hsv(r: f32, g: f32, b: f32) -> [f32; 3] {
     let x_max = r.max(g).max(b);
     let x_min = r.min(g).min(b);
     let c = x_max - x_min;
@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::{
+    colorspace::{ColorModel, ColorSpaceMessage as Message, Component},
+    cvd::Simulation,
+    fl,
+    shaders::{self, hwb as shader},
+};
+
+const COPY_FORMATS: [&str; 4] = [
+    "{h}, {w}, {b}, {a}",
+    "{hex}",
+    "hwb({h} {w%}% {b%}% / {a})",
+    "rgb({rgb.r8} {rgb.g8} {rgb.b8} / {a})",
+];
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "h",
+        label: || fl!("hue"),
+        range: 0.0..=360.0,
+        snap: Some(1.0),
+    },
+    Component {
+        name: "w",
+        label: || fl!("whiteness"),
+        range: 0.0..=1.0,
+        snap: None,
+    },
+    Component {
+        name: "b",
+        label: || fl!("blackness"),
+        range: 0.0..=1.0,
+        snap: None,
+    },
+];
+
+pub struct Hwb;
+
+impl ColorModel for Hwb {
+    fn id(&self) -> &'static str {
+        "hwb"
+    }
+
+    fn name(&self) -> String {
+        fl!("hwb")
+    }
+
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
+    }
+
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        rgb_to_hwb(rgb[0], rgb[1], rgb[2]).to_vec()
+    }
+
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        hwb_to_rgb(values[0], values[1], values[2])
+    }
+
+    fn copy_formats(&self) -> &'static [&'static str] {
+        &COPY_FORMATS
+    }
+
+    fn graph<'a>(
+        &self,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        if !shaders::gpu_available() {
+            return None;
+        }
+
+        Some(
+            cosmic::iced_widget::shader(shader::ColorGraph {
+                hue: values[0],
+                whiteness: values[1],
+                blackness: values[2],
+                cvd,
+            })
+            .width(100)
+            .height(100)
+            .into(),
+        )
+    }
+}
+
+// https://www.w3.org/TR/css-color-4/#hwb-to-rgb
+pub fn hwb_to_rgb(h: f32, w: f32, b: f32) -> [f32; 3] {
+    if w + b >= 1.0 {
+        let gray = w / (w + b);
+        return [gray; 3];
+    }
+
+    super::hsl::hsl_to_rgb(h, 1.0, 0.5).map(|c| c * (1.0 - w - b) + w)
+}
+
+// https://www.w3.org/TR/css-color-4/#rgb-to-hwb
+pub fn rgb_to_hwb(r: f32, g: f32, b: f32) -> [f32; 3] {
+    // HWB shares its hue with HSV
+    let h = super::hsv::rgb_to_hsv(r, g, b)[0];
+    let w = r.min(g).min(b);
+    let black = 1.0 - r.max(g).max(b);
+
+    [h, w, black]
+}
+
+// ---- Tests ----
+#[cfg(test)]
+mod test {
+    use super::{hwb_to_rgb, rgb_to_hwb};
+
+    #[test]
+    fn white() {
+        let rgb = [1f32; 3];
+        let hwb = rgb_to_hwb(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hwb, &[0.0, 1.0, 0.0]));
+
+        let rgb = hwb_to_rgb(hwb[0], hwb[1], hwb[2]);
+        assert!(aprox_eq(&rgb, &[1f32; 3]));
+    }
+
+    #[test]
+    fn black() {
+        let rgb = [0f32; 3];
+        let hwb = rgb_to_hwb(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hwb, &[0.0, 0.0, 1.0]));
+
+        let rgb = hwb_to_rgb(hwb[0], hwb[1], hwb[2]);
+        assert!(aprox_eq(&rgb, &[0f32; 3]));
+    }
+
+    #[test]
+    fn red() {
+        let rgb = [1f32, 0f32, 0f32];
+        let hwb = rgb_to_hwb(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hwb, &[0.0, 0.0, 0.0]));
+
+        let rgb = hwb_to_rgb(hwb[0], hwb[1], hwb[2]);
+        assert!(aprox_eq(&rgb, &[1f32, 0f32, 0f32]));
+    }
+
+    #[test]
+    fn green() {
+        let rgb = [0f32, 1f32, 0f32];
+        let hwb = rgb_to_hwb(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hwb, &[120.0, 0.0, 0.0]));
+
+        let rgb = hwb_to_rgb(hwb[0], hwb[1], hwb[2]);
+        assert!(aprox_eq(&rgb, &[0.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn blue() {
+        let rgb = [0f32, 0f32, 1f32];
+        let hwb = rgb_to_hwb(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hwb, &[240.0, 0.0, 0.0]));
+
+        let rgb = hwb_to_rgb(hwb[0], hwb[1], hwb[2]);
+        assert!(aprox_eq(&rgb, &[0f32, 0f32, 1f32]));
+    }
+
+    #[test]
+    fn steel_blue() {
+        // hwb(207.27 27.45% 29.41%), steelblue
+        let rgb = [70.0 / 255.0, 130.0 / 255.0, 180.0 / 255.0];
+        let hwb = rgb_to_hwb(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&hwb, &[207.27272, 0.27450982, 0.29411763]));
+
+        let back = hwb_to_rgb(hwb[0], hwb[1], hwb[2]);
+        assert!(aprox_eq(&back, &rgb));
+    }
+
+    fn aprox_eq(a: &[f32; 3], b: &[f32; 3]) -> bool {
+        const EPSILON: f32 = 1e-4;
+
+        a[0] > b[0] - EPSILON
+            && a[0] < b[0] + EPSILON
+            && a[1] > b[1] - EPSILON
+            && a[1] < b[1] + EPSILON
+            && a[2] > b[2] - EPSILON
+            && a[2] < b[2] + EPSILON
+    }
+}
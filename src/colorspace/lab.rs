@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::Length;
+
+use crate::{
+    colorspace::{
+        cie::{self, Illuminant},
+        ColorModel, ColorSpaceMessage as Message, Component,
+    },
+    cvd::Simulation,
+    fl,
+    shaders::{self, lab as shader},
+};
+
+const COPY_FORMATS: [&str; 4] = [
+    "{l}, {a}, {b}, {alpha}",
+    "{hex}",
+    "lab({l}% {a} {b} / {alpha})",
+    "rgb({rgb.r8} {rgb.g8} {rgb.b8} / {alpha})",
+];
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "l",
+        label: || fl!("lightness"),
+        range: 0.0..=100.0,
+        snap: None,
+    },
+    Component {
+        name: "a",
+        label: || fl!("green-red"),
+        range: -125.0..=125.0,
+        snap: None,
+    },
+    Component {
+        name: "b",
+        label: || fl!("blue-yellow"),
+        range: -125.0..=125.0,
+        snap: None,
+    },
+];
+
+/// CIE L*a*b*, relative to either white
+pub struct Lab {
+    white: Illuminant,
+}
+
+impl Lab {
+    /// The CSS `lab()` space
+    pub const D50: Lab = Lab {
+        white: Illuminant::D50,
+    };
+    pub const D65: Lab = Lab {
+        white: Illuminant::D65,
+    };
+}
+
+impl ColorModel for Lab {
+    fn id(&self) -> &'static str {
+        match self.white {
+            Illuminant::D50 => "lab",
+            Illuminant::D65 => "lab-d65",
+        }
+    }
+
+    fn name(&self) -> String {
+        match self.white {
+            Illuminant::D50 => fl!("lab-d50"),
+            Illuminant::D65 => fl!("lab-d65"),
+        }
+    }
+
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
+    }
+
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        rgb_to_lab(rgb, self.white).to_vec()
+    }
+
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        lab_to_rgb([values[0], values[1], values[2]], self.white)
+    }
+
+    fn copy_formats(&self) -> &'static [&'static str] {
+        // `lab()` is always relative to D50
+        match self.white {
+            Illuminant::D50 => &COPY_FORMATS,
+            Illuminant::D65 => &[
+                "{l}, {a}, {b}, {alpha}",
+                "{hex}",
+                "lab({lab.l}% {lab.a} {lab.b} / {alpha})",
+                "rgb({rgb.r8} {rgb.g8} {rgb.b8} / {alpha})",
+            ],
+        }
+    }
+
+    fn component_graph<'a>(
+        &self,
+        component: usize,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        if !shaders::gpu_available() {
+            return None;
+        }
+
+        let (lightness, green_red, blue_yellow) = (values[0], values[1], values[2]);
+        let d65 = self.white == Illuminant::D65;
+        let graph = match component {
+            0 => cosmic::iced_widget::shader(shader::ColorGraph::<0> {
+                lightness,
+                green_red,
+                blue_yellow,
+                d65,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            1 => cosmic::iced_widget::shader(shader::ColorGraph::<1> {
+                lightness,
+                green_red,
+                blue_yellow,
+                d65,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            2 => cosmic::iced_widget::shader(shader::ColorGraph::<2> {
+                lightness,
+                green_red,
+                blue_yellow,
+                d65,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            _ => return None,
+        };
+
+        Some(graph)
+    }
+}
+
+pub fn lab_to_rgb(lab: [f32; 3], white: Illuminant) -> [f32; 3] {
+    let xyz = cie::lab_to_xyz(lab, white.white());
+    cie::xyz_to_srgb(white.to_d65(xyz))
+}
+
+pub fn rgb_to_lab(rgb: [f32; 3], white: Illuminant) -> [f32; 3] {
+    let xyz = white.from_d65(cie::srgb_to_xyz(rgb));
+    cie::xyz_to_lab(xyz, white.white())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lab_to_rgb, rgb_to_lab};
+    use crate::colorspace::cie::Illuminant;
+
+    #[test]
+    fn white() {
+        for white in [Illuminant::D50, Illuminant::D65] {
+            let lab = rgb_to_lab([1.0; 3], white);
+            assert!(aprox_eq(&lab, &[100.0, 0.0, 0.0]));
+
+            let rgb = lab_to_rgb(lab, white);
+            assert!(aprox_eq(&rgb, &[1.0; 3]));
+        }
+    }
+
+    #[test]
+    fn black() {
+        let lab = rgb_to_lab([0.0; 3], Illuminant::D50);
+        assert!(aprox_eq(&lab, &[0.0, 0.0, 0.0]));
+
+        let rgb = lab_to_rgb(lab, Illuminant::D50);
+        assert!(aprox_eq(&rgb, &[0.0; 3]));
+    }
+
+    // https://www.w3.org/TR/css-color-4/#specifying-lab-lch
+    #[test]
+    fn d50() {
+        let red = rgb_to_lab([1.0, 0.0, 0.0], Illuminant::D50);
+        assert!(aprox_eq(&red, &[54.2905, 80.8049, 69.8910]));
+
+        let green = rgb_to_lab([0.0, 1.0, 0.0], Illuminant::D50);
+        assert!(aprox_eq(&green, &[87.8185, -79.2711, 80.9946]));
+
+        let blue = rgb_to_lab([0.0, 0.0, 1.0], Illuminant::D50);
+        assert!(aprox_eq(&blue, &[29.5683, 68.2874, -112.0294]));
+
+        let rgb = lab_to_rgb(blue, Illuminant::D50);
+        assert!(aprox_eq(&rgb, &[0.0, 0.0, 1.0]));
+    }
+
+    // http://www.brucelindbloom.com/index.html?ColorCalculator.html
+    #[test]
+    fn d65() {
+        let red = rgb_to_lab([1.0, 0.0, 0.0], Illuminant::D65);
+        assert!(aprox_eq(&red, &[53.2408, 80.0925, 67.2032]));
+
+        let green = rgb_to_lab([0.0, 1.0, 0.0], Illuminant::D65);
+        assert!(aprox_eq(&green, &[87.7347, -86.1827, 83.1793]));
+
+        let blue = rgb_to_lab([0.0, 0.0, 1.0], Illuminant::D65);
+        assert!(aprox_eq(&blue, &[32.2970, 79.1875, -107.8602]));
+
+        let rgb = lab_to_rgb(green, Illuminant::D65);
+        assert!(aprox_eq(&rgb, &[0.0, 1.0, 0.0]));
+    }
+
+    fn aprox_eq(a: &[f32; 3], b: &[f32; 3]) -> bool {
+        const EPSILON: f32 = 1e-2;
+
+        a[0] > b[0] - EPSILON
+            && a[0] < b[0] + EPSILON
+            && a[1] > b[1] - EPSILON
+            && a[1] < b[1] + EPSILON
+            && a[2] > b[2] - EPSILON
+            && a[2] < b[2] + EPSILON
+    }
+}
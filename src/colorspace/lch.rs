@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::Length;
+
+use crate::{
+    colorspace::{
+        cie::Illuminant,
+        lab::{lab_to_rgb, rgb_to_lab},
+        ColorModel, ColorSpaceMessage as Message, Component,
+    },
+    cvd::Simulation,
+    fl,
+    shaders::{self, lch as shader},
+};
+
+const COPY_FORMATS: [&str; 4] = [
+    "{l}, {c}, {h}, {a}",
+    "{hex}",
+    "lch({l}% {c} {h} / {a})",
+    "rgb({r8} {g8} {b8} / {a})",
+];
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "l",
+        label: || fl!("lightness"),
+        range: 0.0..=100.0,
+        snap: None,
+    },
+    Component {
+        name: "c",
+        label: || fl!("chroma"),
+        range: 0.0..=150.0,
+        snap: None,
+    },
+    Component {
+        name: "h",
+        label: || fl!("hue"),
+        range: 0.0..=360.0,
+        snap: Some(1.0),
+    },
+];
+
+/// CIE LCh(ab), the polar form of [`super::lab::Lab`]
+pub struct Lch {
+    white: Illuminant,
+}
+
+impl Lch {
+    /// The CSS `lch()` space
+    pub const D50: Lch = Lch {
+        white: Illuminant::D50,
+    };
+    pub const D65: Lch = Lch {
+        white: Illuminant::D65,
+    };
+}
+
+impl ColorModel for Lch {
+    fn id(&self) -> &'static str {
+        match self.white {
+            Illuminant::D50 => "lch",
+            Illuminant::D65 => "lch-d65",
+        }
+    }
+
+    fn name(&self) -> String {
+        match self.white {
+            Illuminant::D50 => fl!("lch-d50"),
+            Illuminant::D65 => fl!("lch-d65"),
+        }
+    }
+
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
+    }
+
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        rgb_to_lch(rgb, self.white).to_vec()
+    }
+
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        lch_to_rgb([values[0], values[1], values[2]], self.white)
+    }
+
+    fn copy_formats(&self) -> &'static [&'static str] {
+        // `lch()` is always relative to D50
+        match self.white {
+            Illuminant::D50 => &COPY_FORMATS,
+            Illuminant::D65 => &[
+                "{l}, {c}, {h}, {a}",
+                "{hex}",
+                "lch({lch.l}% {lch.c} {lch.h} / {a})",
+                "rgb({r8} {g8} {b8} / {a})",
+            ],
+        }
+    }
+
+    fn component_graph<'a>(
+        &self,
+        component: usize,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        if !shaders::gpu_available() {
+            return None;
+        }
+
+        let (lightness, chroma, hue) = (values[0], values[1], values[2]);
+        let d65 = self.white == Illuminant::D65;
+        let graph = match component {
+            0 => cosmic::iced_widget::shader(shader::ColorGraph::<0> {
+                lightness,
+                chroma,
+                hue,
+                d65,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            1 => cosmic::iced_widget::shader(shader::ColorGraph::<1> {
+                lightness,
+                chroma,
+                hue,
+                d65,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            2 => cosmic::iced_widget::shader(shader::ColorGraph::<2> {
+                lightness,
+                chroma,
+                hue,
+                d65,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            _ => return None,
+        };
+
+        Some(graph)
+    }
+}
+
+pub fn lch_to_rgb(lch: [f32; 3], white: Illuminant) -> [f32; 3] {
+    let h = lch[2].to_radians();
+    lab_to_rgb([lch[0], lch[1] * h.cos(), lch[1] * h.sin()], white)
+}
+
+pub fn rgb_to_lch(rgb: [f32; 3], white: Illuminant) -> [f32; 3] {
+    let lab = rgb_to_lab(rgb, white);
+    let mut lch = [
+        lab[0],
+        (lab[1] * lab[1] + lab[2] * lab[2]).sqrt(),
+        lab[2].atan2(lab[1]).to_degrees(),
+    ];
+
+    if lch[2] < 0.0 {
+        lch[2] += 360.0;
+    }
+
+    lch
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lch_to_rgb, rgb_to_lch};
+    use crate::colorspace::cie::Illuminant;
+
+    #[test]
+    fn white() {
+        for white in [Illuminant::D50, Illuminant::D65] {
+            let lch = rgb_to_lch([1.0; 3], white);
+            assert!(aprox_eq(&lch[..2], &[100.0, 0.0]));
+
+            let rgb = lch_to_rgb(lch, white);
+            assert!(aprox_eq(&rgb, &[1.0; 3]));
+        }
+    }
+
+    // https://www.w3.org/TR/css-color-4/#specifying-lab-lch
+    #[test]
+    fn d50() {
+        let red = rgb_to_lch([1.0, 0.0, 0.0], Illuminant::D50);
+        assert!(aprox_eq(&red, &[54.29, 106.84, 40.85]));
+
+        let rgb = lch_to_rgb(red, Illuminant::D50);
+        assert!(aprox_eq(&rgb, &[1.0, 0.0, 0.0]));
+    }
+
+    // http://www.brucelindbloom.com/index.html?ColorCalculator.html
+    #[test]
+    fn d65() {
+        let red = rgb_to_lch([1.0, 0.0, 0.0], Illuminant::D65);
+        assert!(aprox_eq(&red, &[53.2408, 104.5518, 39.9990]));
+
+        let blue = rgb_to_lch([0.0, 0.0, 1.0], Illuminant::D65);
+        assert!(aprox_eq(&blue, &[32.2970, 133.8076, 306.2849]));
+
+        let rgb = lch_to_rgb(blue, Illuminant::D65);
+        assert!(aprox_eq(&rgb, &[0.0, 0.0, 1.0]));
+    }
+
+    fn aprox_eq(a: &[f32], b: &[f32]) -> bool {
+        const EPSILON: f32 = 1e-2;
+
+        a.iter()
+            .zip(b)
+            .all(|(a, b)| *a > *b - EPSILON && *a < *b + EPSILON)
+    }
+}
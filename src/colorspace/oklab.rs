@@ -1,186 +1,133 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use cosmic::{
-    iced::{gradient::ColorStop, Alignment, Color, Length},
-    widget,
-};
+use cosmic::iced::Length;
 
 use crate::{
-    colorspace::ColorSpaceMessage as Message, fl, shaders::oklab as shader, widgets::color_slider,
+    colorspace::{ColorModel, ColorSpaceMessage as Message, Component},
+    cvd::Simulation,
+    fl,
+    shaders::{
+        self, oklab as shader,
+        raster::{self, Plane},
+    },
 };
 
-const COLOR_STOPS_LIGHTNESS: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 1.0, 1.0),
-    },
+const COPY_FORMATS: [&str; 4] = [
+    "{l}, {a}, {b}, {alpha}",
+    "{hex}",
+    "oklab({l%}% {a} {b} / {alpha})",
+    "rgb({rgb.r8} {rgb.g8} {rgb.b8} / {alpha})",
 ];
-const COLOR_STOPS_GREEN_RED: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 1.0, 0.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 0.0, 0.0),
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "l",
+        label: || fl!("lightness"),
+        range: 0.0..=1.0,
+        snap: None,
     },
-];
-const COLOR_STOPS_BLUE_YELLOW: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 1.0),
+    Component {
+        name: "a",
+        label: || fl!("green-red"),
+        range: -0.5..=0.5,
+        snap: None,
     },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 1.0, 0.0),
+    Component {
+        name: "b",
+        label: || fl!("blue-yellow"),
+        range: -0.5..=0.5,
+        snap: None,
     },
 ];
 
-#[derive(Clone)]
-pub struct Oklab {
-    pub values: [f32; 3],
-    pub strings: [String; 3],
-}
+pub struct Oklab;
 
-impl Oklab {
-    pub fn from_rgb(rgb: [f32; 3]) -> Self {
-        let lab = rgb_to_oklab(rgb[0], rgb[1], rgb[2]);
-
-        Self {
-            strings: [lab[0].to_string(), lab[1].to_string(), lab[2].to_string()],
-            values: lab,
-        }
+impl ColorModel for Oklab {
+    fn id(&self) -> &'static str {
+        "oklab"
     }
 
-    pub fn to_rgb(&self) -> [f32; 3] {
-        oklab_to_rgb(self.values[0], self.values[1], self.values[2])
+    fn name(&self) -> String {
+        fl!("oklab")
     }
 
-    pub fn copy_to_clipboard(&self) -> String {
-        format!("{}, {}, {}", self.values[0], self.values[1], self.values[2])
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
     }
-}
 
-impl Oklab {
-    pub fn change_value(&mut self, index: usize, value: f32) {
-        self.values[index] = value;
-        self.strings[index] = value.to_string();
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        rgb_to_oklab(rgb[0], rgb[1], rgb[2]).to_vec()
     }
 
-    pub fn change_string(&mut self, index: usize, string: String) {
-        if let Ok(value) = string.parse::<f32>() {
-            self.values[index] = value;
-        }
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        oklab_to_rgb(values[0], values[1], values[2])
+    }
 
-        self.strings[index] = string;
+    fn copy_formats(&self) -> &'static [&'static str] {
+        &COPY_FORMATS
     }
 
-    pub fn view<'a>(&self, show_graphs: bool) -> cosmic::Element<'a, Message> {
-        let values = &self.values;
-        let strings = &self.strings;
-
-        let mut lightness = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("lightness")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[0].clone())
-                            .on_input(|string| Message::ChangeString { index: 0, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[0],
-                |value| Message::ChangeValue { index: 0, value },
-                &COLOR_STOPS_LIGHTNESS,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let mut green_red = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("green-red")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[1].clone())
-                            .on_input(|string| Message::ChangeString { index: 1, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                -0.5..=0.5,
-                values[1],
-                |value| Message::ChangeValue { index: 1, value },
-                &COLOR_STOPS_GREEN_RED,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let mut blue_yellow = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("blue-yellow")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[2].clone())
-                            .on_input(|string| Message::ChangeString { index: 2, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                -0.5..=0.5,
-                values[2],
-                |value| Message::ChangeValue { index: 2, value },
-                &COLOR_STOPS_BLUE_YELLOW,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-
-        if show_graphs {
-            lightness = lightness.push(
-                cosmic::iced_widget::shader(shader::ColorGraph::<0> {
-                    lightness: self.values[0],
-                    green_red: self.values[1],
-                    blue_yellow: self.values[2],
-                })
-                .width(Length::Fill),
-            );
-            green_red = green_red.push(
-                cosmic::iced_widget::shader(shader::ColorGraph::<1> {
-                    lightness: self.values[0],
-                    green_red: self.values[1],
-                    blue_yellow: self.values[2],
-                })
-                .width(Length::Fill),
-            );
-            blue_yellow = blue_yellow.push(
-                cosmic::iced_widget::shader(shader::ColorGraph::<2> {
-                    lightness: self.values[0],
-                    green_red: self.values[1],
-                    blue_yellow: self.values[2],
-                })
-                .width(Length::Fill),
-            );
+    fn component_graph<'a>(
+        &self,
+        component: usize,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        let (lightness, green_red, blue_yellow) = (values[0], values[1], values[2]);
+        if !shaders::gpu_available() {
+            let plane = Plane::Oklab {
+                lightness,
+                green_red,
+                blue_yellow,
+                mode: component as u32,
+            };
+            return (component < 3).then(|| raster::component_graph(plane, cvd));
         }
 
-        let content = widget::column::with_capacity(3)
-            .push(widget::container(lightness).class(cosmic::style::Container::Card))
-            .push(widget::container(green_red).class(cosmic::style::Container::Card))
-            .push(widget::container(blue_yellow).class(cosmic::style::Container::Card))
-            .spacing(10.0);
-
-        content.into()
+        let graph = match component {
+            0 => cosmic::iced_widget::shader(shader::ColorGraph::<0> {
+                lightness,
+                green_red,
+                blue_yellow,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            1 => cosmic::iced_widget::shader(shader::ColorGraph::<1> {
+                lightness,
+                green_red,
+                blue_yellow,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            2 => cosmic::iced_widget::shader(shader::ColorGraph::<2> {
+                lightness,
+                green_red,
+                blue_yellow,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            _ => return None,
+        };
+
+        Some(graph)
     }
 }
 
+pub fn oklab_to_rgb(l: f32, a: f32, b: f32) -> [f32; 3] {
+    oklab_to_linear_rgb(l, a, b).map(super::cie::linear_to_srgb)
+}
+
+pub fn rgb_to_oklab(r: f32, g: f32, b: f32) -> [f32; 3] {
+    let [r, g, b] = [r, g, b].map(super::cie::srgb_to_linear);
+    linear_rgb_to_oklab(r, g, b)
+}
+
 // https://bottosson.github.io/posts/oklab/
 #[allow(clippy::excessive_precision)]
-pub fn oklab_to_rgb(l: f32, a: f32, b: f32) -> [f32; 3] {
+pub fn oklab_to_linear_rgb(l: f32, a: f32, b: f32) -> [f32; 3] {
     let l_ = l + 0.3963377774 * a + 0.2158037573 * b;
     let m_ = l - 0.1055613458 * a - 0.0638541728 * b;
     let s_ = l - 0.0894841775 * a - 1.2914855480 * b;
@@ -198,7 +145,7 @@ pub fn oklab_to_rgb(l: f32, a: f32, b: f32) -> [f32; 3] {
 
 // https://bottosson.github.io/posts/oklab/
 #[allow(clippy::excessive_precision)]
-pub fn rgb_to_oklab(r: f32, g: f32, b: f32) -> [f32; 3] {
+pub fn linear_rgb_to_oklab(r: f32, g: f32, b: f32) -> [f32; 3] {
     let l = 0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b;
     let m = 0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b;
     let s = 0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b;
@@ -268,6 +215,16 @@ mod test {
         assert!(aprox_eq(&rgb, &[0f32, 0f32, 1f32]));
     }
 
+    #[test]
+    fn gray() {
+        let rgb = [0.5f32; 3];
+        let lab = rgb_to_oklab(rgb[0], rgb[1], rgb[2]);
+        assert!(aprox_eq(&lab, &[0.5981807, 0.0, 0.0]));
+
+        let rgb = oklab_to_rgb(lab[0], lab[1], lab[2]);
+        assert!(aprox_eq(&rgb, &[0.5f32; 3]));
+    }
+
     fn aprox_eq(a: &[f32; 3], b: &[f32; 3]) -> bool {
         const EPSILON: f32 = 1e-4;
 
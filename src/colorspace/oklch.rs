@@ -1,204 +1,122 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use cosmic::{
-    iced::{gradient::ColorStop, Alignment, Color, Length},
-    widget,
-};
+use cosmic::iced::Length;
 
 use crate::{
-    colorspace::ColorSpaceMessage as Message, fl, shaders::oklch as shader, widgets::color_slider,
+    colorspace::{ColorModel, ColorSpaceMessage as Message, Component},
+    cvd::Simulation,
+    fl,
+    shaders::{
+        self, oklch as shader,
+        raster::{self, Plane},
+    },
 };
 
-const COLOR_STOPS_LIGHTNESS: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 1.0, 1.0),
-    },
-];
-const COLOR_STOPS_CHROMA: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 0.0, 0.0),
-    },
+const COPY_FORMATS: [&str; 4] = [
+    "{l}, {c}, {h}, {a}",
+    "{hex}",
+    "oklch({l%}% {c} {h} / {a})",
+    "rgb({r8} {g8} {b8} / {a})",
 ];
-const COLOR_STOPS_HUE: [ColorStop; 7] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.92, 0.0, 0.22),
-    },
-    ColorStop {
-        offset: 0.0812,
-        color: Color::from_rgb(1.0, 0.0, 0.0),
-    },
-    ColorStop {
-        offset: 0.304,
-        color: Color::from_rgb(1.0, 1.0, 0.0),
-    },
-    ColorStop {
-        offset: 0.395,
-        color: Color::from_rgb(0.0, 1.0, 0.0),
-    },
-    ColorStop {
-        offset: 0.541,
-        color: Color::from_rgb(0.0, 1.0, 1.0),
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "l",
+        label: || fl!("lightness"),
+        range: 0.0..=1.0,
+        snap: None,
     },
-    ColorStop {
-        offset: 0.733,
-        color: Color::from_rgb(0.0, 0.0, 1.0),
+    Component {
+        name: "c",
+        label: || fl!("chroma"),
+        range: 0.0..=0.37,
+        snap: None,
     },
-    ColorStop {
-        offset: 0.912,
-        color: Color::from_rgb(1.0, 0.0, 1.0),
+    Component {
+        name: "h",
+        label: || fl!("hue"),
+        range: 0.0..=360.0,
+        snap: Some(1.0),
     },
 ];
 
-#[derive(Clone)]
-pub struct Oklch {
-    pub values: [f32; 3],
-    pub strings: [String; 3],
-}
-
-impl Oklch {
-    pub fn from_rgb(rgb: [f32; 3]) -> Self {
-        let lch = rgb_to_oklch(rgb[0], rgb[1], rgb[2]);
+pub struct Oklch;
 
-        Self {
-            strings: [lch[0].to_string(), lch[1].to_string(), lch[2].to_string()],
-            values: lch,
-        }
+impl ColorModel for Oklch {
+    fn id(&self) -> &'static str {
+        "oklch"
     }
 
-    pub fn to_rgb(&self) -> [f32; 3] {
-        oklch_to_rgb(self.values[0], self.values[1], self.values[2])
+    fn name(&self) -> String {
+        fl!("oklch")
     }
 
-    pub fn copy_to_clipboard(&self) -> String {
-        format!("{}, {}, {}", self.values[0], self.values[1], self.values[2])
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
     }
-}
 
-impl Oklch {
-    pub fn change_value(&mut self, index: usize, value: f32) {
-        self.values[index] = value;
-        self.strings[index] = value.to_string();
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        rgb_to_oklch(rgb[0], rgb[1], rgb[2]).to_vec()
     }
 
-    pub fn change_string(&mut self, index: usize, string: String) {
-        if let Ok(value) = string.parse::<f32>() {
-            self.values[index] = value;
-        }
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        oklch_to_rgb(values[0], values[1], values[2])
+    }
 
-        self.strings[index] = string;
+    fn copy_formats(&self) -> &'static [&'static str] {
+        &COPY_FORMATS
     }
 
-    pub fn view<'a>(&self, show_graphs: bool) -> cosmic::Element<'a, Message> {
-        let values = &self.values;
-        let strings = &self.strings;
-
-        let mut lightness = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("lightness")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[0].clone())
-                            .on_input(|string| Message::ChangeString { index: 0, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1.0f32,
-                values[0],
-                |value| Message::ChangeValue { index: 0, value },
-                &COLOR_STOPS_LIGHTNESS,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let mut chroma = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("chroma")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[1].clone())
-                            .on_input(|string| Message::ChangeString { index: 1, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=0.37f32,
-                values[1],
-                |value| Message::ChangeValue { index: 1, value },
-                &COLOR_STOPS_CHROMA,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let mut hue = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("hue")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[2].clone())
-                            .on_input(|string| Message::ChangeString { index: 2, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=360f32,
-                values[2],
-                |value| Message::ChangeValue { index: 2, value },
-                &COLOR_STOPS_HUE,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-
-        if show_graphs {
-            lightness = lightness.push(
-                cosmic::iced_widget::shader(shader::ColorGraph::<0> {
-                    lightness: self.values[0],
-                    chroma: self.values[1],
-                    hue: self.values[2],
-                })
-                .width(Length::Fill),
-            );
-            chroma = chroma.push(
-                cosmic::iced_widget::shader(shader::ColorGraph::<1> {
-                    lightness: self.values[0],
-                    chroma: self.values[1],
-                    hue: self.values[2],
-                })
-                .width(Length::Fill),
-            );
-            hue = hue.push(
-                cosmic::iced_widget::shader(shader::ColorGraph::<2> {
-                    lightness: self.values[0],
-                    chroma: self.values[1],
-                    hue: self.values[2],
-                })
-                .width(Length::Fill),
-            );
+    fn component_graph<'a>(
+        &self,
+        component: usize,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        let (lightness, chroma, hue) = (values[0], values[1], values[2]);
+        if !shaders::gpu_available() {
+            let plane = Plane::Oklch {
+                lightness,
+                chroma,
+                hue,
+                mode: component as u32,
+            };
+            return (component < 3).then(|| raster::component_graph(plane, cvd));
         }
 
-        let content = widget::column::with_capacity(3)
-            .push(widget::container(lightness).class(cosmic::style::Container::Card))
-            .push(widget::container(chroma).class(cosmic::style::Container::Card))
-            .push(widget::container(hue).class(cosmic::style::Container::Card))
-            .spacing(10.0);
-
-        content.into()
+        let graph = match component {
+            0 => cosmic::iced_widget::shader(shader::ColorGraph::<0> {
+                lightness,
+                chroma,
+                hue,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            1 => cosmic::iced_widget::shader(shader::ColorGraph::<1> {
+                lightness,
+                chroma,
+                hue,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            2 => cosmic::iced_widget::shader(shader::ColorGraph::<2> {
+                lightness,
+                chroma,
+                hue,
+                cvd,
+            })
+            .width(Length::Fill)
+            .into(),
+            _ => return None,
+        };
+
+        Some(graph)
     }
 }
 
-fn oklch_to_rgb(l: f32, c: f32, h: f32) -> [f32; 3] {
+pub fn oklch_to_rgb(l: f32, c: f32, h: f32) -> [f32; 3] {
     let h = h.to_radians();
     let a = c * h.cos();
     let b = c * h.sin();
@@ -206,7 +124,7 @@ fn oklch_to_rgb(l: f32, c: f32, h: f32) -> [f32; 3] {
     super::oklab::oklab_to_rgb(l, a, b)
 }
 
-fn rgb_to_oklch(r: f32, g: f32, b: f32) -> [f32; 3] {
+pub fn rgb_to_oklch(r: f32, g: f32, b: f32) -> [f32; 3] {
     let lab = super::oklab::rgb_to_oklab(r, g, b);
     let mut lch = [
         lab[0],
@@ -1,157 +1,185 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use cosmic::{
-    iced::{gradient::ColorStop, Alignment, Color},
-    widget,
+use crate::{
+    colorspace::{cie::RgbSpace, ColorModel, Component},
+    fl,
 };
 
-use crate::{colorspace::ColorSpaceMessage as Message, fl, widgets::color_slider};
-
-const COLOR_STOPS_RED: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(1.0, 0.0, 0.0),
-    },
+const COPY_FORMATS: [&str; 9] = [
+    "{r}, {g}, {b}, {a}",
+    "{hex}",
+    "rgb({r8} {g8} {b8} / {a})",
+    "{r8}, {g8}, {b8}, {a8}",
+    "{r%}%, {g%}%, {b%}%, {a%}%",
+    "Color::from_rgb({r:.3}, {g:.3}, {b:.3})",
+    "Color::from_rgba({r:.3}, {g:.3}, {b:.3}, {a:.3})",
+    "vec3({r:.3}, {g:.3}, {b:.3})",
+    "vec4({r:.3}, {g:.3}, {b:.3}, {a:.3})",
 ];
-const COLOR_STOPS_GREEN: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
-    },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(0.0, 1.0, 0.0),
+
+/// Copy formats of the RGB spaces besides sRGB, `{hex}` and `rgb()` are
+/// always sRGB.
+macro_rules! working_space_formats {
+    ($name:literal) => {
+        [
+            "{r}, {g}, {b}, {a}",
+            concat!("color(", $name, " {r} {g} {b} / {a})"),
+            "{r%}%, {g%}%, {b%}%, {a%}%",
+            "vec3({r:.3}, {g:.3}, {b:.3})",
+            "vec4({r:.3}, {g:.3}, {b:.3}, {a:.3})",
+            "{hex}",
+            "rgb({rgb.r8} {rgb.g8} {rgb.b8} / {a})",
+        ]
+    };
+}
+
+const COPY_FORMATS_SRGB_LINEAR: [&str; 7] = working_space_formats!("srgb-linear");
+const COPY_FORMATS_DISPLAY_P3: [&str; 7] = working_space_formats!("display-p3");
+const COPY_FORMATS_A98_RGB: [&str; 7] = working_space_formats!("a98-rgb");
+const COPY_FORMATS_PROPHOTO_RGB: [&str; 7] = working_space_formats!("prophoto-rgb");
+const COPY_FORMATS_REC2020: [&str; 7] = working_space_formats!("rec2020");
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "r",
+        label: || fl!("red"),
+        range: 0.0..=1.0,
+        snap: Some(1.0 / 255.0),
     },
-];
-const COLOR_STOPS_BLUE: [ColorStop; 2] = [
-    ColorStop {
-        offset: 0.0,
-        color: Color::from_rgb(0.0, 0.0, 0.0),
+    Component {
+        name: "g",
+        label: || fl!("green"),
+        range: 0.0..=1.0,
+        snap: Some(1.0 / 255.0),
     },
-    ColorStop {
-        offset: 1.0,
-        color: Color::from_rgb(0.0, 0.0, 1.0),
+    Component {
+        name: "b",
+        label: || fl!("blue"),
+        range: 0.0..=1.0,
+        snap: Some(1.0 / 255.0),
     },
 ];
 
-#[derive(Clone)]
+/// RGB in one of the CSS predefined working spaces
 pub struct Rgb {
-    pub values: [f32; 3],
-    pub strings: [String; 3],
+    space: RgbSpace,
 }
 
-impl Default for Rgb {
-    fn default() -> Self {
-        Self {
-            values: [1.0; 3],
-            strings: ["1".into(), "1".into(), "1".into()],
-        }
+impl Rgb {
+    pub const SRGB: Self = Self::new(RgbSpace::Srgb);
+    pub const SRGB_LINEAR: Self = Self::new(RgbSpace::SrgbLinear);
+    pub const DISPLAY_P3: Self = Self::new(RgbSpace::DisplayP3);
+    pub const A98_RGB: Self = Self::new(RgbSpace::A98Rgb);
+    pub const PROPHOTO_RGB: Self = Self::new(RgbSpace::ProphotoRgb);
+    pub const REC2020: Self = Self::new(RgbSpace::Rec2020);
+
+    const fn new(space: RgbSpace) -> Self {
+        Self { space }
     }
 }
 
-impl Rgb {
-    pub fn from_rgb(rgb: [f32; 3]) -> Self {
-        Self {
-            strings: [rgb[0].to_string(), rgb[1].to_string(), rgb[2].to_string()],
-            values: rgb,
+impl ColorModel for Rgb {
+    fn id(&self) -> &'static str {
+        match self.space {
+            RgbSpace::Srgb => "rgb",
+            space => space.css_name(),
         }
     }
 
-    pub fn to_rgb(&self) -> [f32; 3] {
-        self.values
+    fn name(&self) -> String {
+        match self.space {
+            RgbSpace::Srgb => fl!("rgb"),
+            RgbSpace::SrgbLinear => fl!("srgb-linear"),
+            RgbSpace::DisplayP3 => fl!("display-p3"),
+            RgbSpace::A98Rgb => fl!("a98-rgb"),
+            RgbSpace::ProphotoRgb => fl!("prophoto-rgb"),
+            RgbSpace::Rec2020 => fl!("rec2020"),
+        }
     }
 
-    pub fn copy_to_clipboard(&self) -> String {
-        format!("{}, {}, {}", self.values[0], self.values[1], self.values[2])
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
     }
-}
 
-impl Rgb {
-    pub fn change_value(&mut self, index: usize, value: f32) {
-        self.values[index] = value;
-        self.strings[index] = value.to_string();
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        self.space.from_srgb(rgb).to_vec()
+    }
+
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        self.space.to_srgb([values[0], values[1], values[2]])
+    }
+
+    fn gamut(&self) -> RgbSpace {
+        self.space
     }
 
-    pub fn change_string(&mut self, index: usize, string: String) {
-        if let Ok(value) = string.parse::<f32>() {
-            self.values[index] = value;
+    fn copy_formats(&self) -> &'static [&'static str] {
+        match self.space {
+            RgbSpace::Srgb => &COPY_FORMATS,
+            RgbSpace::SrgbLinear => &COPY_FORMATS_SRGB_LINEAR,
+            RgbSpace::DisplayP3 => &COPY_FORMATS_DISPLAY_P3,
+            RgbSpace::A98Rgb => &COPY_FORMATS_A98_RGB,
+            RgbSpace::ProphotoRgb => &COPY_FORMATS_PROPHOTO_RGB,
+            RgbSpace::Rec2020 => &COPY_FORMATS_REC2020,
         }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colorspace::cie::RgbSpace;
+
+    #[test]
+    fn white() {
+        for space in [
+            RgbSpace::SrgbLinear,
+            RgbSpace::DisplayP3,
+            RgbSpace::A98Rgb,
+            RgbSpace::ProphotoRgb,
+            RgbSpace::Rec2020,
+        ] {
+            let rgb = space.from_srgb([1.0; 3]);
+            assert!(aprox_eq(&rgb, &[1.0; 3]), "{space:?}");
+        }
+    }
 
-        self.strings[index] = string;
+    #[test]
+    fn srgb_linear() {
+        let rgb = RgbSpace::SrgbLinear.from_srgb([0.5, 0.0, 1.0]);
+        assert!(aprox_eq(&rgb, &[0.2140, 0.0, 1.0]));
     }
 
-    pub fn view<'a>(&self, _show_graphs: bool) -> cosmic::Element<'a, Message> {
-        let values = &self.values;
-        let strings = &self.strings;
-
-        let red = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("red")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[0].clone())
-                            .on_input(|string| Message::ChangeString { index: 0, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[0],
-                |value| Message::ChangeValue { index: 0, value },
-                &COLOR_STOPS_RED,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let green = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("green")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[1].clone())
-                            .on_input(|string| Message::ChangeString { index: 1, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[1],
-                |value| Message::ChangeValue { index: 1, value },
-                &COLOR_STOPS_GREEN,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-        let blue = widget::column::with_capacity(3)
-            .push(
-                widget::row::with_capacity(2)
-                    .push(widget::text(fl!("blue")).size(20.0))
-                    .push(
-                        widget::text_input("", strings[2].clone())
-                            .on_input(|string| Message::ChangeString { index: 2, string }),
-                    )
-                    .align_y(Alignment::Center)
-                    .spacing(10.0),
-            )
-            .push(color_slider(
-                0f32..=1f32,
-                values[2],
-                |value| Message::ChangeValue { index: 2, value },
-                &COLOR_STOPS_BLUE,
-            ))
-            .spacing(10.0)
-            .padding(10.0);
-
-        let content = widget::column::with_capacity(3)
-            .push(widget::container(red).class(cosmic::style::Container::Card))
-            .push(widget::container(green).class(cosmic::style::Container::Card))
-            .push(widget::container(blue).class(cosmic::style::Container::Card))
-            .spacing(10.0);
-
-        content.into()
+    // https://www.w3.org/TR/css-color-4/#predefined-display-p3
+    #[test]
+    fn red() {
+        let p3 = RgbSpace::DisplayP3.from_srgb([1.0, 0.0, 0.0]);
+        assert!(aprox_eq(&p3, &[0.9175, 0.2003, 0.1386]));
+
+        let rec2020 = RgbSpace::Rec2020.from_srgb([1.0, 0.0, 0.0]);
+        assert!(aprox_eq(&rec2020, &[0.7920, 0.2310, 0.0738]));
+
+        let rgb = RgbSpace::DisplayP3.to_srgb(p3);
+        assert!(aprox_eq(&rgb, &[1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn wide_gamut() {
+        // Display P3 green is outside of sRGB
+        let rgb = RgbSpace::DisplayP3.to_srgb([0.0, 1.0, 0.0]);
+        assert!(rgb[0] < 0.0 && rgb[1] > 1.0);
+
+        let p3 = RgbSpace::DisplayP3.from_srgb(rgb);
+        assert!(aprox_eq(&p3, &[0.0, 1.0, 0.0]));
+    }
+
+    fn aprox_eq(a: &[f32; 3], b: &[f32; 3]) -> bool {
+        const EPSILON: f32 = 1e-3;
+
+        a[0] > b[0] - EPSILON
+            && a[0] < b[0] + EPSILON
+            && a[1] > b[1] - EPSILON
+            && a[1] < b[1] + EPSILON
+            && a[2] > b[2] - EPSILON
+            && a[2] < b[2] + EPSILON
     }
 }
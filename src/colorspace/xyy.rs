@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::Length;
+
+use crate::{
+    colorspace::{
+        cie,
+        xyz::{xyy_to_xyz, xyz_to_xyy},
+        ColorModel, ColorSpaceMessage as Message, Component,
+    },
+    cvd::Simulation,
+    fl,
+    shaders::{self, chromaticity as shader},
+};
+
+const COPY_FORMATS: [&str; 4] = [
+    "{x}, {y}, {Y}, {a}",
+    "{hex}",
+    "color(xyz-d65 {xyz.x} {xyz.y} {xyz.z} / {a})",
+    "rgb({r8} {g8} {b8} / {a})",
+];
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "x",
+        label: || fl!("chromaticity-x"),
+        range: 0.0..=0.8,
+        snap: None,
+    },
+    Component {
+        name: "y",
+        label: || fl!("chromaticity-y"),
+        range: 0.0..=0.9,
+        snap: None,
+    },
+    Component {
+        name: "Y",
+        label: || fl!("luminance"),
+        range: 0.0..=1.0,
+        snap: None,
+    },
+];
+
+/// CIE 1931 xyY, chromaticity and luminance relative to D65
+pub struct XyY;
+
+impl ColorModel for XyY {
+    fn id(&self) -> &'static str {
+        "xyy"
+    }
+
+    fn name(&self) -> String {
+        fl!("xyy")
+    }
+
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
+    }
+
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        xyz_to_xyy(cie::srgb_to_xyz(rgb)).to_vec()
+    }
+
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        cie::xyz_to_srgb(xyy_to_xyz([values[0], values[1], values[2]]))
+    }
+
+    fn copy_formats(&self) -> &'static [&'static str] {
+        &COPY_FORMATS
+    }
+
+    fn graph<'a>(
+        &self,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        if !shaders::gpu_available() {
+            return None;
+        }
+
+        Some(
+            cosmic::iced_widget::shader(shader::ColorGraph {
+                x: values[0],
+                y: values[1],
+                cvd,
+            })
+            .width(Length::Fill)
+            .height(240)
+            .into(),
+        )
+    }
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::Length;
+
+use crate::{
+    colorspace::{cie, ColorModel, ColorSpaceMessage as Message, Component},
+    cvd::Simulation,
+    fl,
+    shaders::{self, chromaticity as shader},
+};
+
+const COPY_FORMATS: [&str; 4] = [
+    "{x}, {y}, {z}, {a}",
+    "{hex}",
+    "color(xyz-d65 {x} {y} {z} / {a})",
+    "rgb({r8} {g8} {b8} / {a})",
+];
+
+static COMPONENTS: [Component; 3] = [
+    Component {
+        name: "x",
+        label: || fl!("tristimulus-x"),
+        range: 0.0..=1.0,
+        snap: None,
+    },
+    Component {
+        name: "y",
+        label: || fl!("tristimulus-y"),
+        range: 0.0..=1.0,
+        snap: None,
+    },
+    Component {
+        name: "z",
+        label: || fl!("tristimulus-z"),
+        range: 0.0..=1.1,
+        snap: None,
+    },
+];
+
+/// CIE 1931 XYZ relative to D65, the CSS `xyz-d65` space
+pub struct Xyz;
+
+impl ColorModel for Xyz {
+    fn id(&self) -> &'static str {
+        "xyz"
+    }
+
+    fn name(&self) -> String {
+        fl!("xyz")
+    }
+
+    fn components(&self) -> &'static [Component] {
+        &COMPONENTS
+    }
+
+    fn from_rgb(&self, rgb: [f32; 3]) -> Vec<f32> {
+        cie::srgb_to_xyz(rgb).to_vec()
+    }
+
+    fn to_rgb(&self, values: &[f32]) -> [f32; 3] {
+        cie::xyz_to_srgb([values[0], values[1], values[2]])
+    }
+
+    fn copy_formats(&self) -> &'static [&'static str] {
+        &COPY_FORMATS
+    }
+
+    fn graph<'a>(
+        &self,
+        values: &[f32],
+        cvd: Option<Simulation>,
+    ) -> Option<cosmic::Element<'a, Message>> {
+        if !shaders::gpu_available() {
+            return None;
+        }
+
+        let [x, y, _] = xyz_to_xyy([values[0], values[1], values[2]]);
+        Some(
+            cosmic::iced_widget::shader(shader::ColorGraph { x, y, cvd })
+                .width(Length::Fill)
+                .height(240)
+                .into(),
+        )
+    }
+}
+
+/// The chromaticity of D65, used for black
+pub const WHITE_XY: [f32; 2] = [0.3127, 0.3290];
+
+pub fn xyz_to_xyy(xyz: [f32; 3]) -> [f32; 3] {
+    let sum = xyz[0] + xyz[1] + xyz[2];
+    if sum.abs() <= f32::EPSILON {
+        return [WHITE_XY[0], WHITE_XY[1], xyz[1]];
+    }
+
+    [xyz[0] / sum, xyz[1] / sum, xyz[1]]
+}
+
+pub fn xyy_to_xyz(xyy: [f32; 3]) -> [f32; 3] {
+    let [x, y, luminance] = xyy;
+    if y.abs() <= f32::EPSILON {
+        return [0.0; 3];
+    }
+
+    [x * luminance / y, luminance, (1.0 - x - y) * luminance / y]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{xyy_to_xyz, xyz_to_xyy};
+    use crate::colorspace::cie::{srgb_to_xyz, xyz_to_srgb};
+
+    #[test]
+    fn white() {
+        let xyz = srgb_to_xyz([1.0; 3]);
+        assert!(aprox_eq(&xyz, &[0.9505, 1.0, 1.0891]));
+
+        let xyy = xyz_to_xyy(xyz);
+        assert!(aprox_eq(&xyy, &[0.3127, 0.3290, 1.0]));
+
+        let rgb = xyz_to_srgb(xyy_to_xyz(xyy));
+        assert!(aprox_eq(&rgb, &[1.0; 3]));
+    }
+
+    #[test]
+    fn black() {
+        let xyy = xyz_to_xyy(srgb_to_xyz([0.0; 3]));
+        assert!(aprox_eq(&xyy, &[0.3127, 0.3290, 0.0]));
+
+        let xyz = xyy_to_xyz(xyy);
+        assert!(aprox_eq(&xyz, &[0.0; 3]));
+    }
+
+    // The sRGB primaries, https://www.color.org/chardata/rgb/srgb.xalter
+    #[test]
+    fn primaries() {
+        let red = srgb_to_xyz([1.0, 0.0, 0.0]);
+        assert!(aprox_eq(&red, &[0.4124, 0.2126, 0.0193]));
+        assert!(aprox_eq(&xyz_to_xyy(red), &[0.64, 0.33, 0.2126]));
+
+        let green = xyz_to_xyy(srgb_to_xyz([0.0, 1.0, 0.0]));
+        assert!(aprox_eq(&green, &[0.30, 0.60, 0.7152]));
+
+        let blue = xyz_to_xyy(srgb_to_xyz([0.0, 0.0, 1.0]));
+        assert!(aprox_eq(&blue, &[0.15, 0.06, 0.0722]));
+
+        let rgb = xyz_to_srgb(xyy_to_xyz(blue));
+        assert!(aprox_eq(&rgb, &[0.0, 0.0, 1.0]));
+    }
+
+    fn aprox_eq(a: &[f32; 3], b: &[f32; 3]) -> bool {
+        const EPSILON: f32 = 1e-3;
+
+        a[0] > b[0] - EPSILON
+            && a[0] < b[0] + EPSILON
+            && a[1] > b[1] - EPSILON
+            && a[1] < b[1] + EPSILON
+            && a[2] > b[2] - EPSILON
+            && a[2] < b[2] + EPSILON
+    }
+}
@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+
+use cosmic::{
+    cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry},
+    Application,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::ColorPicker,
+    colorspace::{self, ColorSpace, PickerLayout},
+    delta_e::DeltaE,
+    format::DEFAULT_PRECISION,
+};
+
+/// The largest selectable copy precision
+pub const MAX_PRECISION: usize = 6;
+
+#[derive(Clone, CosmicConfigEntry, Debug, PartialEq)]
+#[version = 1]
+pub struct Config {
+    pub show_graphs: bool,
+    /// Round hue sliders to whole degrees and RGB sliders to 8-bit values
+    pub snap_sliders: bool,
+    /// The model id of newly added columns
+    pub default_space: String,
+    /// The selected copy format of each model id, see `ColorPicker::copy_formats`
+    pub copy_formats: HashMap<String, usize>,
+    pub custom_formats: Vec<String>,
+    /// Decimals of copy format numbers without an explicit precision
+    pub precision: usize,
+    pub out_of_gamut_copy: OutOfGamutCopy,
+    pub delta_e: DeltaE,
+    /// Differences below this are marked as perceptually identical
+    pub delta_e_threshold: f32,
+    pub spaces: Vec<SavedSpace>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            show_graphs: false,
+            snap_sliders: false,
+            default_space: ColorSpace::default().model().id().to_string(),
+            copy_formats: HashMap::new(),
+            custom_formats: vec![],
+            precision: DEFAULT_PRECISION,
+            out_of_gamut_copy: OutOfGamutCopy::default(),
+            delta_e: DeltaE::default(),
+            delta_e_threshold: DeltaE::default().just_noticeable(),
+            spaces: vec![SavedSpace::from(&ColorSpace::default())],
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config, falling back to the defaults of anything that
+    /// can't be read.
+    pub fn load() -> (Option<cosmic_config::Config>, Self) {
+        let handler = match cosmic_config::Config::new(ColorPicker::APP_ID, Self::VERSION) {
+            Ok(handler) => handler,
+            Err(err) => {
+                log::error!("Failed to open config: {}", err);
+                return (None, Self::default());
+            }
+        };
+
+        let config = match Self::get_entry(&handler) {
+            Ok(config) => config,
+            Err((errors, config)) => {
+                for err in errors {
+                    log::warn!("Failed to load config: {}", err);
+                }
+                config
+            }
+        };
+
+        (Some(handler), config)
+    }
+}
+
+/// What copying a color outside of its model's gamut does
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutOfGamutCopy {
+    #[default]
+    Copy,
+    Clamp,
+    Refuse,
+}
+
+impl OutOfGamutCopy {
+    /// In the order of the settings dropdown
+    pub const ALL: [Self; 3] = [Self::Copy, Self::Clamp, Self::Refuse];
+}
+
+/// A column's model id and its components, alpha included
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedSpace {
+    pub space: String,
+    pub values: Vec<f32>,
+    #[serde(default)]
+    pub layout: PickerLayout,
+}
+
+impl From<&ColorSpace> for SavedSpace {
+    fn from(space: &ColorSpace) -> Self {
+        Self {
+            space: space.model().id().to_string(),
+            values: space.values().to_vec(),
+            layout: space.layout(),
+        }
+    }
+}
+
+impl SavedSpace {
+    pub fn to_space(&self) -> Option<ColorSpace> {
+        let mut space = ColorSpace::from_values(colorspace::model(&self.space)?, &self.values)?;
+        space.set_layout(self.layout);
+        Some(space)
+    }
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! WCAG 2.1 relative luminance and contrast ratios.
+//! https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+
+use crate::colorspace::cie::srgb_to_linear;
+
+/// Minimum ratio of normal text for level AA
+pub const AA_NORMAL: f32 = 4.5;
+/// Minimum ratio of large text for level AA
+pub const AA_LARGE: f32 = 3.0;
+/// Minimum ratio of normal text for level AAA
+pub const AAA_NORMAL: f32 = 7.0;
+/// Minimum ratio of large text for level AAA
+pub const AAA_LARGE: f32 = 4.5;
+
+/// Relative luminance of sRGB, clamped into gamut
+pub fn relative_luminance(rgb: [f32; 3]) -> f32 {
+    let [r, g, b] = rgb.map(|c| srgb_to_linear(c.clamp(0.0, 1.0)));
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Blends a translucent foreground over an opaque background
+pub fn composite(foreground: [f32; 4], background: [f32; 3]) -> [f32; 3] {
+    let alpha = foreground[3].clamp(0.0, 1.0);
+    [0, 1, 2].map(|i| foreground[i] * alpha + background[i] * (1.0 - alpha))
+}
+
+/// The contrast ratio, from 1 to 21, of `foreground` over `background`.
+/// The background's alpha is ignored.
+pub fn contrast_ratio(foreground: [f32; 4], background: [f32; 4]) -> f32 {
+    let background = [background[0], background[1], background[2]];
+    let foreground = relative_luminance(composite(foreground, background));
+    let background = relative_luminance(background);
+
+    let (lighter, darker) = if foreground > background {
+        (foreground, background)
+    } else {
+        (background, foreground)
+    };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{contrast_ratio, relative_luminance};
+
+    #[test]
+    fn extremes() {
+        assert!(aprox_eq(contrast_ratio([0.0, 0.0, 0.0, 1.0], [1.0; 4]), 21.0));
+        assert!(aprox_eq(contrast_ratio([1.0; 4], [0.0, 0.0, 0.0, 1.0]), 21.0));
+        assert!(aprox_eq(contrast_ratio([0.3, 0.6, 0.9, 1.0], [0.3, 0.6, 0.9, 1.0]), 1.0));
+    }
+
+    #[test]
+    fn grays() {
+        // #777777 just fails AA on white, #767676 passes
+        let gray = |c: f32| [c / 255.0, c / 255.0, c / 255.0, 1.0];
+        assert!(aprox_eq(contrast_ratio(gray(119.0), [1.0; 4]), 4.48));
+        assert!(aprox_eq(contrast_ratio(gray(118.0), [1.0; 4]), 4.54));
+    }
+
+    #[test]
+    fn luminance() {
+        assert!(aprox_eq(relative_luminance([1.0, 0.0, 0.0]), 0.2126));
+        assert!(aprox_eq(relative_luminance([0.5, 0.5, 0.5]), 0.2140));
+        assert!(aprox_eq(relative_luminance([1.5, -0.5, 0.0]), 0.2126));
+    }
+
+    #[test]
+    fn translucent() {
+        // Half transparent black over white is a mid gray
+        let ratio = contrast_ratio([0.0, 0.0, 0.0, 0.5], [1.0; 4]);
+        assert!(aprox_eq(ratio, contrast_ratio([0.5, 0.5, 0.5, 1.0], [1.0; 4])));
+    }
+
+    fn aprox_eq(a: f32, b: f32) -> bool {
+        const EPSILON: f32 = 1e-2;
+
+        a > b - EPSILON && a < b + EPSILON
+    }
+}
@@ -0,0 +1,629 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Parsing of CSS Color Level 4 `<color>` values.
+//! https://www.w3.org/TR/css-color-4/
+
+use crate::colorspace::{
+    cie::{self, Illuminant, RgbSpace},
+    hsl_to_rgb, hwb_to_rgb, lab_to_rgb, oklab_to_rgb,
+};
+use crate::hex::parse_hex;
+
+/// The space a parsed color's components are expressed in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CssSpace {
+    /// `rgb()`, hex and named colors, components in 0..1
+    Srgb,
+    /// `hsl()`, hue in degrees, saturation and lightness in 0..1
+    Hsl,
+    /// `hwb()`, hue in degrees, whiteness and blackness in 0..1
+    Hwb,
+    /// `lab()`, relative to D50
+    Lab,
+    /// `lch()`, relative to D50
+    Lch,
+    Oklab,
+    Oklch,
+    /// `color()` with one of the predefined RGB spaces
+    Rgb(RgbSpace),
+    /// `color(xyz-d50 ...)`
+    XyzD50,
+    /// `color(xyz ...)` and `color(xyz-d65 ...)`
+    XyzD65,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CssColor {
+    pub space: CssSpace,
+    pub components: [f32; 3],
+    pub alpha: f32,
+}
+
+impl CssColor {
+    /// Converts to (possibly out of gamut) sRGB with alpha
+    pub fn to_rgb(&self) -> [f32; 4] {
+        let [c0, c1, c2] = self.components;
+        let rgb = match self.space {
+            CssSpace::Srgb => self.components,
+            CssSpace::Hsl => hsl_to_rgb(c0, c1, c2),
+            CssSpace::Hwb => hwb_to_rgb(c0, c1, c2),
+            CssSpace::Lab => lab_to_rgb(self.components, Illuminant::D50),
+            CssSpace::Lch => {
+                let h = c2.to_radians();
+                lab_to_rgb([c0, c1 * h.cos(), c1 * h.sin()], Illuminant::D50)
+            }
+            CssSpace::Oklab => oklab_to_rgb(c0, c1, c2),
+            CssSpace::Oklch => {
+                let h = c2.to_radians();
+                oklab_to_rgb(c0, c1 * h.cos(), c1 * h.sin())
+            }
+            CssSpace::Rgb(space) => space.to_srgb(self.components),
+            CssSpace::XyzD50 => cie::xyz_to_srgb(cie::d50_to_d65(self.components)),
+            CssSpace::XyzD65 => cie::xyz_to_srgb(self.components),
+        };
+
+        [rgb[0], rgb[1], rgb[2], self.alpha]
+    }
+}
+
+/// Parses any CSS Color 4 `<color>` except `currentcolor` and system colors.
+pub fn parse_css(input: &str) -> Option<CssColor> {
+    let input = input.trim().to_ascii_lowercase();
+
+    if input.starts_with('#') {
+        let rgba = parse_hex(&input)?;
+        return Some(CssColor {
+            space: CssSpace::Srgb,
+            components: [rgba[0], rgba[1], rgba[2]],
+            alpha: rgba[3],
+        });
+    }
+
+    let Some((function, arguments)) = input.split_once('(') else {
+        return parse_named(&input);
+    };
+    let arguments = arguments.strip_suffix(')')?;
+
+    match function.trim_end() {
+        "rgb" | "rgba" => parse_rgb(arguments),
+        "hsl" | "hsla" => parse_hsl(arguments),
+        "hwb" => {
+            let (c, alpha) = parse_modern(arguments)?;
+            Some(CssColor {
+                space: CssSpace::Hwb,
+                components: [
+                    c[0].hue()?,
+                    c[1].percentage(100.0)?.clamp(0.0, 1.0),
+                    c[2].percentage(100.0)?.clamp(0.0, 1.0),
+                ],
+                alpha,
+            })
+        }
+        "lab" => {
+            let (c, alpha) = parse_modern(arguments)?;
+            Some(CssColor {
+                space: CssSpace::Lab,
+                components: [
+                    c[0].number(100.0)?.clamp(0.0, 100.0),
+                    c[1].number(125.0)?,
+                    c[2].number(125.0)?,
+                ],
+                alpha,
+            })
+        }
+        "lch" => {
+            let (c, alpha) = parse_modern(arguments)?;
+            Some(CssColor {
+                space: CssSpace::Lch,
+                components: [
+                    c[0].number(100.0)?.clamp(0.0, 100.0),
+                    c[1].number(150.0)?.max(0.0),
+                    c[2].hue()?,
+                ],
+                alpha,
+            })
+        }
+        "oklab" => {
+            let (c, alpha) = parse_modern(arguments)?;
+            Some(CssColor {
+                space: CssSpace::Oklab,
+                components: [
+                    c[0].number(1.0)?.clamp(0.0, 1.0),
+                    c[1].number(0.4)?,
+                    c[2].number(0.4)?,
+                ],
+                alpha,
+            })
+        }
+        "oklch" => {
+            let (c, alpha) = parse_modern(arguments)?;
+            Some(CssColor {
+                space: CssSpace::Oklch,
+                components: [
+                    c[0].number(1.0)?.clamp(0.0, 1.0),
+                    c[1].number(0.4)?.max(0.0),
+                    c[2].hue()?,
+                ],
+                alpha,
+            })
+        }
+        "color" => parse_color_function(arguments),
+        _ => None,
+    }
+}
+
+fn parse_rgb(arguments: &str) -> Option<CssColor> {
+    let (c, alpha) = parse_arguments(arguments)?;
+    let channel =
+        |value: Value| -> Option<f32> { Some((value.number(255.0)? / 255.0).clamp(0.0, 1.0)) };
+
+    Some(CssColor {
+        space: CssSpace::Srgb,
+        components: [channel(c[0])?, channel(c[1])?, channel(c[2])?],
+        alpha,
+    })
+}
+
+fn parse_hsl(arguments: &str) -> Option<CssColor> {
+    let (c, alpha) = parse_arguments(arguments)?;
+
+    Some(CssColor {
+        space: CssSpace::Hsl,
+        components: [
+            c[0].hue()?,
+            c[1].percentage(100.0)?.clamp(0.0, 1.0),
+            c[2].percentage(100.0)?.clamp(0.0, 1.0),
+        ],
+        alpha,
+    })
+}
+
+fn parse_color_function(arguments: &str) -> Option<CssColor> {
+    let arguments = arguments.trim_start();
+    let split = arguments.find(|c: char| c.is_ascii_whitespace())?;
+    let (space, arguments) = arguments.split_at(split);
+
+    let space = match space {
+        "srgb" => CssSpace::Rgb(RgbSpace::Srgb),
+        "srgb-linear" => CssSpace::Rgb(RgbSpace::SrgbLinear),
+        "display-p3" => CssSpace::Rgb(RgbSpace::DisplayP3),
+        "a98-rgb" => CssSpace::Rgb(RgbSpace::A98Rgb),
+        "prophoto-rgb" => CssSpace::Rgb(RgbSpace::ProphotoRgb),
+        "rec2020" => CssSpace::Rgb(RgbSpace::Rec2020),
+        "xyz-d50" => CssSpace::XyzD50,
+        "xyz" | "xyz-d65" => CssSpace::XyzD65,
+        _ => return None,
+    };
+
+    let (c, alpha) = parse_modern(arguments)?;
+    let components = [c[0].number(1.0)?, c[1].number(1.0)?, c[2].number(1.0)?];
+
+    Some(match space {
+        CssSpace::Rgb(RgbSpace::Srgb) => CssColor {
+            space: CssSpace::Srgb,
+            components,
+            alpha,
+        },
+        space => CssColor {
+            space,
+            components,
+            alpha,
+        },
+    })
+}
+
+fn parse_named(name: &str) -> Option<CssColor> {
+    if name == "transparent" {
+        return Some(CssColor {
+            space: CssSpace::Srgb,
+            components: [0.0; 3],
+            alpha: 0.0,
+        });
+    }
+
+    let index = NAMED_COLORS
+        .binary_search_by(|(named, _)| named.cmp(&name))
+        .ok()?;
+    let rgb = NAMED_COLORS[index].1;
+
+    Some(CssColor {
+        space: CssSpace::Srgb,
+        components: [rgb >> 16, rgb >> 8, rgb].map(|c| (c & 0xFF) as f32 / 255.0),
+        alpha: 1.0,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Value {
+    None,
+    Number(f32),
+    Percentage(f32),
+    /// In degrees
+    Angle(f32),
+}
+
+impl Value {
+    fn parse(token: &str) -> Option<Self> {
+        if token == "none" {
+            return Some(Value::None);
+        }
+
+        if let Some(number) = token.strip_suffix('%') {
+            return parse_number(number).map(Value::Percentage);
+        }
+
+        for (unit, degrees) in [
+            ("deg", 1.0),
+            ("grad", 0.9),
+            ("rad", 180.0 / std::f32::consts::PI),
+            ("turn", 360.0),
+        ] {
+            if let Some(number) = token.strip_suffix(unit) {
+                return parse_number(number).map(|n| Value::Angle(n * degrees));
+            }
+        }
+
+        parse_number(token).map(Value::Number)
+    }
+
+    /// A number, where 100% is `reference`
+    fn number(self, reference: f32) -> Option<f32> {
+        match self {
+            Value::None => Some(0.0),
+            Value::Number(n) => Some(n),
+            Value::Percentage(p) => Some(p / 100.0 * reference),
+            Value::Angle(_) => None,
+        }
+    }
+
+    /// A percentage in 0..1, where a bare number is out of `reference`
+    fn percentage(self, reference: f32) -> Option<f32> {
+        match self {
+            Value::None => Some(0.0),
+            Value::Number(n) => Some(n / reference),
+            Value::Percentage(p) => Some(p / 100.0),
+            Value::Angle(_) => None,
+        }
+    }
+
+    /// A hue in degrees, normalized to 0..360
+    fn hue(self) -> Option<f32> {
+        match self {
+            Value::None => Some(0.0),
+            Value::Number(degrees) | Value::Angle(degrees) => Some(degrees.rem_euclid(360.0)),
+            Value::Percentage(_) => None,
+        }
+    }
+}
+
+fn parse_number(token: &str) -> Option<f32> {
+    // Rust also accepts `inf` and `NaN`, CSS doesn't.
+    let valid = token
+        .bytes()
+        .all(|c| c.is_ascii_digit() || matches!(c, b'.' | b'e' | b'+' | b'-'))
+        && token.bytes().any(|c| c.is_ascii_digit());
+
+    if valid {
+        token.parse::<f32>().ok()
+    } else {
+        None
+    }
+}
+
+/// Parses either the legacy comma separated or the modern space separated
+/// arguments of `rgb()` and `hsl()`.
+fn parse_arguments(arguments: &str) -> Option<([Value; 3], f32)> {
+    if !arguments.contains(',') {
+        return parse_modern(arguments);
+    }
+
+    let mut values = arguments.split(',').map(|token| match token.trim() {
+        // `none` isn't allowed in the legacy syntax
+        "none" => None,
+        token => Value::parse(token),
+    });
+
+    let components = [values.next()??, values.next()??, values.next()??];
+    let alpha = match values.next() {
+        Some(alpha) => parse_alpha(alpha?)?,
+        None => 1.0,
+    };
+
+    if values.next().is_some() {
+        return None;
+    }
+
+    Some((components, alpha))
+}
+
+/// Parses space separated components with an optional `/ alpha`.
+fn parse_modern(arguments: &str) -> Option<([Value; 3], f32)> {
+    let (components, alpha) = match arguments.split_once('/') {
+        Some((components, alpha)) => {
+            let mut alpha = alpha.split_ascii_whitespace();
+            let value = Value::parse(alpha.next()?)?;
+            if alpha.next().is_some() {
+                return None;
+            }
+
+            (components, parse_alpha(value)?)
+        }
+        None => (arguments, 1.0),
+    };
+
+    let mut values = components.split_ascii_whitespace().map(Value::parse);
+    let components = [values.next()??, values.next()??, values.next()??];
+    if values.next().is_some() {
+        return None;
+    }
+
+    Some((components, alpha))
+}
+
+fn parse_alpha(value: Value) -> Option<f32> {
+    Some(value.number(1.0)?.clamp(0.0, 1.0))
+}
+
+/// https://www.w3.org/TR/css-color-4/#named-colors, sorted by name
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
+#[cfg(test)]
+mod test {
+    use super::{parse_css, CssColor, CssSpace, NAMED_COLORS};
+    use crate::colorspace::cie::RgbSpace;
+
+    #[test]
+    fn named() {
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+
+        let color = parse_css("RebeccaPurple").unwrap();
+        assert!(aprox_eq(&color.to_rgb(), &[0.4, 0.2, 0.6, 1.0]));
+        assert_eq!(parse_css("transparent").unwrap().alpha, 0.0);
+        assert_eq!(parse_css("notacolor"), None);
+    }
+
+    #[test]
+    fn hex() {
+        let color = parse_css("#ff000080").unwrap();
+        assert_eq!(color.space, CssSpace::Srgb);
+        assert!(aprox_eq(&color.to_rgb(), &[1.0, 0.0, 0.0, 128.0 / 255.0]));
+    }
+
+    #[test]
+    fn rgb() {
+        let modern = parse_css("rgb(12 34 56 / 50%)").unwrap();
+        let legacy = parse_css("rgba(12, 34, 56, 0.5)").unwrap();
+        assert_eq!(modern, legacy);
+        assert!(aprox_eq(
+            &modern.to_rgb(),
+            &[12.0 / 255.0, 34.0 / 255.0, 56.0 / 255.0, 0.5]
+        ));
+
+        let percent = parse_css("rgb(100% 50% none)").unwrap();
+        assert!(aprox_eq(&percent.to_rgb(), &[1.0, 0.5, 0.0, 1.0]));
+
+        assert_eq!(parse_css("rgb(1, 2, none)"), None);
+        assert_eq!(parse_css("rgb(1 2)"), None);
+        assert_eq!(parse_css("rgb(1 2 3 4)"), None);
+        assert_eq!(parse_css("rgb(inf 2 3)"), None);
+    }
+
+    #[test]
+    fn hsl() {
+        let color = parse_css("hsl(210deg 40% 50%)").unwrap();
+        assert_eq!(color.space, CssSpace::Hsl);
+        // #4d80b3
+        assert!(aprox_eq(&color.to_rgb(), &[0.3, 0.5, 0.7, 1.0]));
+
+        let turn = parse_css("hsla(0.5turn, 100%, 50%, 25%)").unwrap();
+        assert!(aprox_eq(&turn.to_rgb(), &[0.0, 1.0, 1.0, 0.25]));
+    }
+
+    #[test]
+    fn hwb() {
+        let color = parse_css("hwb(120 20% 30%)").unwrap();
+        assert!(aprox_eq(&color.to_rgb(), &[0.2, 0.7, 0.2, 1.0]));
+
+        let gray = parse_css("hwb(0 60% 60%)").unwrap();
+        assert!(aprox_eq(&gray.to_rgb(), &[0.5, 0.5, 0.5, 1.0]));
+    }
+
+    #[test]
+    fn lab() {
+        let color = parse_css("lab(54.2905 80.8049 69.891)").unwrap();
+        assert!(aprox_eq_eps(&color.to_rgb(), &[1.0, 0.0, 0.0, 1.0], 1e-3));
+
+        let lch = parse_css("lch(54.2905 106.8390 40.8526deg)").unwrap();
+        assert!(aprox_eq_eps(&lch.to_rgb(), &[1.0, 0.0, 0.0, 1.0], 1e-3));
+    }
+
+    #[test]
+    fn oklch() {
+        let color = parse_css("oklch(70% 0.1 250)").unwrap();
+        assert_eq!(
+            color,
+            CssColor {
+                space: CssSpace::Oklch,
+                components: [0.7, 0.1, 250.0],
+                alpha: 1.0
+            }
+        );
+
+        let red = parse_css("oklab(0.62796 0.22486 0.12585)").unwrap();
+        assert!(aprox_eq_eps(&red.to_rgb(), &[1.0, 0.0, 0.0, 1.0], 1e-3));
+    }
+
+    #[test]
+    fn color_function() {
+        let color = parse_css("color(display-p3 0.9175 0.2003 0.1386 / 0.5)").unwrap();
+        assert_eq!(color.space, CssSpace::Rgb(RgbSpace::DisplayP3));
+        assert!(aprox_eq_eps(&color.to_rgb(), &[1.0, 0.0, 0.0, 0.5], 1e-3));
+
+        let srgb = parse_css("color(srgb 100% 0 0)").unwrap();
+        assert_eq!(srgb.space, CssSpace::Srgb);
+
+        let xyz = parse_css("color(xyz 0.9504559 1 1.0890578)").unwrap();
+        assert!(aprox_eq(&xyz.to_rgb(), &[1.0; 4]));
+
+        assert_eq!(parse_css("color(unknown 1 1 1)"), None);
+    }
+
+    fn aprox_eq(a: &[f32; 4], b: &[f32; 4]) -> bool {
+        aprox_eq_eps(a, b, 1e-4)
+    }
+
+    fn aprox_eq_eps(a: &[f32; 4], b: &[f32; 4], epsilon: f32) -> bool {
+        a.iter()
+            .zip(b)
+            .all(|(a, b)| *a > *b - epsilon && *a < *b + epsilon)
+    }
+}
@@ -0,0 +1,297 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Color vision deficiency simulation.
+//!
+//! Machado, Oliveira and Fernandes, "A Physiologically-based Model for
+//! Simulation of Color Vision Deficiency", 2009.
+//! https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html
+
+use cosmic::iced::Color;
+
+use crate::colorspace::cie::{self, Matrix};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Deficiency {
+    /// Missing or anomalous L cones, protanomaly and protanopia
+    Protan,
+    /// Missing or anomalous M cones, deuteranomaly and deuteranopia
+    Deutan,
+    /// Missing or anomalous S cones, tritanomaly and tritanopia
+    Tritan,
+}
+
+impl Deficiency {
+    /// In the order of the deficiency dropdown
+    pub const ALL: [Self; 3] = [Self::Protan, Self::Deutan, Self::Tritan];
+}
+
+/// A deficiency at a severity from 0 (normal vision) to 1 (dichromacy)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Simulation {
+    pub deficiency: Deficiency,
+    pub severity: f32,
+}
+
+impl Simulation {
+    /// The matrix applied to linear sRGB, interpolated between the
+    /// severities of the paper, which are 0.1 apart.
+    pub fn matrix(&self) -> Matrix {
+        let matrices = match self.deficiency {
+            Deficiency::Protan => &PROTAN,
+            Deficiency::Deutan => &DEUTAN,
+            Deficiency::Tritan => &TRITAN,
+        };
+
+        let position = self.severity.clamp(0.0, 1.0) * 10.0;
+        let index = (position.floor() as usize).min(9);
+        let t = position - index as f32;
+
+        let (a, b) = (&matrices[index], &matrices[index + 1]);
+        [0, 1, 2].map(|row| [0, 1, 2].map(|column| a[row][column] * (1.0 - t) + b[row][column] * t))
+    }
+
+    /// Simulates sRGB as seen with the deficiency
+    pub fn apply(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let linear = rgb.map(cie::srgb_to_linear);
+        cie::mul(&self.matrix(), linear).map(cie::linear_to_srgb)
+    }
+
+    pub fn apply_color(&self, color: Color) -> Color {
+        let [r, g, b] = self.apply([color.r, color.g, color.b]);
+        Color::from_rgba(r, g, b, color.a)
+    }
+}
+
+const IDENTITY: Matrix = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+/// Severities 0.0 to 1.0
+const PROTAN: [Matrix; 11] = [
+    IDENTITY,
+    [
+        [0.856167, 0.182038, -0.038205],
+        [0.029342, 0.955115, 0.015544],
+        [-0.002880, -0.001563, 1.004443],
+    ],
+    [
+        [0.734766, 0.334872, -0.069637],
+        [0.051840, 0.919198, 0.028963],
+        [-0.004928, -0.004209, 1.009137],
+    ],
+    [
+        [0.630323, 0.465641, -0.095964],
+        [0.069181, 0.890046, 0.040773],
+        [-0.006308, -0.007724, 1.014032],
+    ],
+    [
+        [0.539009, 0.579343, -0.118352],
+        [0.082546, 0.866121, 0.051332],
+        [-0.007136, -0.011959, 1.019095],
+    ],
+    [
+        [0.458064, 0.679578, -0.137642],
+        [0.092785, 0.846313, 0.060902],
+        [-0.007494, -0.016807, 1.024301],
+    ],
+    [
+        [0.385450, 0.769005, -0.154455],
+        [0.100526, 0.829802, 0.069673],
+        [-0.007442, -0.022190, 1.029632],
+    ],
+    [
+        [0.319627, 0.849633, -0.169261],
+        [0.106241, 0.815969, 0.077790],
+        [-0.007025, -0.028051, 1.035076],
+    ],
+    [
+        [0.259411, 0.923008, -0.182420],
+        [0.110296, 0.804340, 0.085364],
+        [-0.006276, -0.034346, 1.040622],
+    ],
+    [
+        [0.203876, 0.990338, -0.194214],
+        [0.112975, 0.794542, 0.092483],
+        [-0.005222, -0.041043, 1.046265],
+    ],
+    [
+        [0.152286, 1.052583, -0.204868],
+        [0.114503, 0.786281, 0.099216],
+        [-0.003882, -0.048116, 1.051998],
+    ],
+];
+
+/// Severities 0.0 to 1.0
+const DEUTAN: [Matrix; 11] = [
+    IDENTITY,
+    [
+        [0.866435, 0.177704, -0.044139],
+        [0.049567, 0.939063, 0.011370],
+        [-0.003453, 0.007233, 0.996220],
+    ],
+    [
+        [0.760729, 0.319078, -0.079807],
+        [0.090568, 0.889315, 0.020117],
+        [-0.006027, 0.013325, 0.992702],
+    ],
+    [
+        [0.675425, 0.433850, -0.109275],
+        [0.125303, 0.847755, 0.026942],
+        [-0.007950, 0.018572, 0.989378],
+    ],
+    [
+        [0.605511, 0.528560, -0.134071],
+        [0.155318, 0.812366, 0.032316],
+        [-0.009376, 0.023176, 0.986200],
+    ],
+    [
+        [0.547494, 0.607765, -0.155259],
+        [0.181692, 0.781742, 0.036566],
+        [-0.010410, 0.027275, 0.983136],
+    ],
+    [
+        [0.498864, 0.674741, -0.173604],
+        [0.205199, 0.754872, 0.039929],
+        [-0.011131, 0.030969, 0.980162],
+    ],
+    [
+        [0.457771, 0.731899, -0.189670],
+        [0.226409, 0.731012, 0.042579],
+        [-0.011595, 0.034333, 0.977261],
+    ],
+    [
+        [0.422823, 0.781057, -0.203881],
+        [0.245752, 0.709602, 0.044646],
+        [-0.011843, 0.037423, 0.974421],
+    ],
+    [
+        [0.392952, 0.823610, -0.216562],
+        [0.263559, 0.690210, 0.046232],
+        [-0.011910, 0.040281, 0.971630],
+    ],
+    [
+        [0.367322, 0.860646, -0.227968],
+        [0.280085, 0.672501, 0.047413],
+        [-0.011820, 0.042940, 0.968881],
+    ],
+];
+
+/// Severities 0.0 to 1.0
+const TRITAN: [Matrix; 11] = [
+    IDENTITY,
+    [
+        [0.926670, 0.092514, -0.019184],
+        [0.021191, 0.964503, 0.014306],
+        [0.008437, 0.054813, 0.936750],
+    ],
+    [
+        [0.895720, 0.133330, -0.029050],
+        [0.029997, 0.945400, 0.024603],
+        [0.013027, 0.104707, 0.882266],
+    ],
+    [
+        [0.905871, 0.127791, -0.033662],
+        [0.026856, 0.941251, 0.031893],
+        [0.013410, 0.148296, 0.838294],
+    ],
+    [
+        [0.948035, 0.089490, -0.037526],
+        [0.014364, 0.946792, 0.038844],
+        [0.010853, 0.193991, 0.795156],
+    ],
+    [
+        [1.017277, 0.027029, -0.044306],
+        [-0.006113, 0.958479, 0.047634],
+        [0.006379, 0.248708, 0.744913],
+    ],
+    [
+        [1.104996, -0.046633, -0.058363],
+        [-0.032137, 0.971635, 0.060503],
+        [0.001336, 0.317922, 0.680742],
+    ],
+    [
+        [1.193214, -0.109812, -0.083402],
+        [-0.058496, 0.979410, 0.079086],
+        [-0.002346, 0.403492, 0.598854],
+    ],
+    [
+        [1.257728, -0.139648, -0.118081],
+        [-0.078003, 0.975409, 0.102594],
+        [-0.003316, 0.501214, 0.502102],
+    ],
+    [
+        [1.278864, -0.125333, -0.153531],
+        [-0.084748, 0.957674, 0.127074],
+        [-0.000989, 0.601151, 0.399838],
+    ],
+    [
+        [1.255528, -0.076749, -0.178779],
+        [-0.078411, 0.930809, 0.147602],
+        [0.004733, 0.691367, 0.303900],
+    ],
+];
+
+#[cfg(test)]
+mod test {
+    use super::{Deficiency, Simulation, DEUTAN, PROTAN, TRITAN};
+
+    #[test]
+    fn white_is_kept() {
+        for matrix in PROTAN.iter().chain(&DEUTAN).chain(&TRITAN) {
+            for row in matrix {
+                assert!((row.iter().sum::<f32>() - 1.0).abs() < 1e-5, "{row:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn normal_vision() {
+        for deficiency in Deficiency::ALL {
+            let simulation = Simulation {
+                deficiency,
+                severity: 0.0,
+            };
+            assert!(aprox_eq(
+                &simulation.apply([0.2, 0.4, 0.6]),
+                &[0.2, 0.4, 0.6]
+            ));
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        let simulation = |severity| Simulation {
+            deficiency: Deficiency::Deutan,
+            severity,
+        };
+
+        assert_eq!(simulation(1.0).matrix(), DEUTAN[10]);
+        assert_eq!(simulation(0.3).matrix()[1], DEUTAN[3][1]);
+
+        let halfway = simulation(0.35).matrix();
+        assert!((halfway[0][0] - (DEUTAN[3][0][0] + DEUTAN[4][0][0]) / 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn protanopia() {
+        // Red and green become hard to tell apart
+        let simulation = Simulation {
+            deficiency: Deficiency::Protan,
+            severity: 1.0,
+        };
+        let red = simulation.apply([1.0, 0.0, 0.0]);
+        assert!(red[0] < 0.5 && red[1] > 0.3);
+
+        let gray = simulation.apply([0.5; 3]);
+        assert!(aprox_eq(&gray, &[0.5; 3]));
+    }
+
+    fn aprox_eq(a: &[f32; 3], b: &[f32; 3]) -> bool {
+        const EPSILON: f32 = 1e-3;
+
+        a[0] > b[0] - EPSILON
+            && a[0] < b[0] + EPSILON
+            && a[1] > b[1] - EPSILON
+            && a[1] < b[1] + EPSILON
+            && a[2] > b[2] - EPSILON
+            && a[2] < b[2] + EPSILON
+    }
+}
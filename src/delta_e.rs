@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Color difference metrics.
+//! http://www2.ece.rochester.edu/~gsharma/ciede2000/
+
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::colorspace::{cie::Illuminant, rgb_to_lab, rgb_to_oklab};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeltaE {
+    /// CIE76, Euclidean distance in CIELAB
+    E76,
+    /// CIE94 with the graphic arts weights
+    E94,
+    /// CIEDE2000
+    #[default]
+    E2000,
+    /// Euclidean distance in OKLab
+    Ok,
+}
+
+impl DeltaE {
+    /// In the order of the metric dropdown
+    pub const ALL: [Self; 4] = [Self::E76, Self::E94, Self::E2000, Self::Ok];
+
+    /// A difference below which two colors are usually indistinguishable
+    pub fn just_noticeable(self) -> f32 {
+        match self {
+            DeltaE::E76 => 2.3,
+            DeltaE::E94 | DeltaE::E2000 => 1.0,
+            DeltaE::Ok => 0.02,
+        }
+    }
+
+    /// The difference between two sRGB colors, CIELAB is relative to D50
+    pub fn between(self, a: [f32; 3], b: [f32; 3]) -> f32 {
+        let lab = |rgb| rgb_to_lab(rgb, Illuminant::D50);
+        match self {
+            DeltaE::E76 => delta_e76(lab(a), lab(b)),
+            DeltaE::E94 => delta_e94(lab(a), lab(b)),
+            DeltaE::E2000 => delta_e2000(lab(a), lab(b)),
+            DeltaE::Ok => delta_eok(
+                rgb_to_oklab(a[0], a[1], a[2]),
+                rgb_to_oklab(b[0], b[1], b[2]),
+            ),
+        }
+    }
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+pub fn delta_e76(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    distance(lab1, lab2)
+}
+
+/// Euclidean distance in OKLab
+pub fn delta_eok(oklab1: [f32; 3], oklab2: [f32; 3]) -> f32 {
+    distance(oklab1, oklab2)
+}
+
+/// CIE94 with `lab1` as the reference
+pub fn delta_e94(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    const K1: f32 = 0.045;
+    const K2: f32 = 0.015;
+
+    let c1 = lab1[1].hypot(lab1[2]);
+    let c2 = lab2[1].hypot(lab2[2]);
+
+    let dl = lab1[0] - lab2[0];
+    let dc = c1 - c2;
+    let da = lab1[1] - lab2[1];
+    let db = lab1[2] - lab2[2];
+    // Rounding can make this slightly negative
+    let dh2 = (da * da + db * db - dc * dc).max(0.0);
+
+    let sc = 1.0 + K1 * c1;
+    let sh = 1.0 + K2 * c1;
+
+    (dl * dl + (dc / sc).powi(2) + dh2 / (sh * sh)).sqrt()
+}
+
+pub fn delta_e2000(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    let [l1, a1, b1] = lab1;
+    let [l2, a2, b2] = lab2;
+
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let c7 = c_mean.powi(7);
+    let g = 0.5 * (1.0 - (c7 / (c7 + 25f32.powi(7))).sqrt());
+
+    let a1 = a1 * (1.0 + g);
+    let a2 = a2 * (1.0 + g);
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+
+    let hue = |a: f32, b: f32| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+
+    let l50 = (l_mean - 50.0).powi(2);
+    let sl = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let sc = 1.0 + 0.045 * c_mean;
+    let sh = 1.0 + 0.015 * c_mean * t;
+
+    let c7 = c_mean.powi(7);
+    let rc = 2.0 * (c7 / (c7 + 25f32.powi(7))).sqrt();
+    let d_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let rt = -(2.0 * d_theta * PI / 180.0).sin() * rc;
+
+    let dl = dl / sl;
+    let dc = dc / sc;
+    let dh = dh / sh;
+    (dl * dl + dc * dc + dh * dh + rt * dc * dh).sqrt()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{delta_e2000, delta_e76, delta_e94, delta_eok, DeltaE};
+
+    // Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference Formula",
+    // table 1. Pairs 10 and 14 sit exactly on the hue discontinuity, which
+    // f32 can't resolve, and are left out.
+    const SHARMA: [([f32; 3], [f32; 3], f32); 32] = [
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+        ([50.0, 2.8361, -74.0200], [50.0, 0.0, -82.7485], 3.4412),
+        ([50.0, -1.3802, -84.2814], [50.0, 0.0, -82.7485], 1.0),
+        ([50.0, -1.1848, -84.8006], [50.0, 0.0, -82.7485], 1.0),
+        ([50.0, -0.9009, -85.5211], [50.0, 0.0, -82.7485], 1.0),
+        ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+        ([50.0, -1.0, 2.0], [50.0, 0.0, 0.0], 2.3669),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0009], 7.1792),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0011], 7.2195),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0012], 7.2195),
+        ([50.0, -0.001, 2.49], [50.0, 0.0009, -2.49], 4.8045),
+        ([50.0, -0.001, 2.49], [50.0, 0.0011, -2.49], 4.7461),
+        ([50.0, 2.5, 0.0], [50.0, 0.0, -2.5], 4.3065),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        ([50.0, 2.5, 0.0], [61.0, -5.0, 29.0], 22.8977),
+        ([50.0, 2.5, 0.0], [56.0, -27.0, -3.0], 31.9030),
+        ([50.0, 2.5, 0.0], [58.0, 24.0, 15.0], 19.4535),
+        ([50.0, 2.5, 0.0], [50.0, 3.1736, 0.5854], 1.0),
+        ([50.0, 2.5, 0.0], [50.0, 3.2972, 0.0], 1.0),
+        ([50.0, 2.5, 0.0], [50.0, 1.8634, 0.5757], 1.0),
+        ([50.0, 2.5, 0.0], [50.0, 3.2592, 0.3350], 1.0),
+        (
+            [60.2574, -34.0099, 36.2677],
+            [60.4626, -34.1751, 39.4387],
+            1.2644,
+        ),
+        (
+            [63.0109, -31.0961, -5.8663],
+            [62.8187, -29.7946, -4.0864],
+            1.2630,
+        ),
+        (
+            [61.2901, 3.7196, -5.3901],
+            [61.4292, 2.2480, -4.9620],
+            1.8731,
+        ),
+        (
+            [35.0831, -44.1164, 3.7933],
+            [35.0232, -40.0716, 1.5901],
+            1.8645,
+        ),
+        (
+            [22.7233, 20.0904, -46.6940],
+            [23.0331, 14.9730, -42.5619],
+            2.0373,
+        ),
+        (
+            [36.4612, 47.8580, 18.3852],
+            [36.2715, 50.5065, 21.2231],
+            1.4146,
+        ),
+        (
+            [90.8027, -2.0831, 1.4410],
+            [91.1528, -1.6435, 0.0447],
+            1.4441,
+        ),
+        (
+            [90.9257, -0.5406, -0.9208],
+            [88.6381, -0.8985, -0.7239],
+            1.5381,
+        ),
+        (
+            [6.7747, -0.2908, -2.4247],
+            [5.8714, -0.0985, -2.2286],
+            0.6377,
+        ),
+        (
+            [2.0776, 0.0795, -1.1350],
+            [0.9033, -0.0636, -0.5514],
+            0.9082,
+        ),
+    ];
+
+    #[test]
+    fn ciede2000() {
+        for (index, (lab1, lab2, expected)) in SHARMA.iter().enumerate() {
+            let difference = delta_e2000(*lab1, *lab2);
+            assert!(aprox_eq(difference, *expected), "{index}: {difference}");
+            assert!(aprox_eq(delta_e2000(*lab2, *lab1), *expected), "{index}");
+        }
+    }
+
+    #[test]
+    fn cie76_and_cie94() {
+        assert!(aprox_eq(delta_e76([50.0, 0.0, 0.0], [50.0, 3.0, 4.0]), 5.0));
+
+        // Lightness is unweighted, chroma and hue differences shrink
+        assert!(aprox_eq(
+            delta_e94([50.0, 0.0, 0.0], [60.0, 0.0, 0.0]),
+            10.0
+        ));
+        let (lab1, lab2) = ([50.0, 40.0, 20.0], [50.0, 45.0, 10.0]);
+        assert!(delta_e94(lab1, lab2) < delta_e76(lab1, lab2));
+    }
+
+    #[test]
+    fn oklab() {
+        assert!(aprox_eq(delta_eok([1.0, 0.0, 0.0], [0.0, 0.0, 0.0]), 1.0));
+        assert!(aprox_eq(DeltaE::Ok.between([1.0; 3], [0.0; 3]), 1.0));
+        assert!(aprox_eq(DeltaE::E2000.between([1.0; 3], [0.0; 3]), 100.0));
+    }
+
+    fn aprox_eq(a: f32, b: f32) -> bool {
+        const EPSILON: f32 = 1e-3;
+
+        a > b - EPSILON && a < b + EPSILON
+    }
+}
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Copy format templates.
+//!
+//! A template is plain text with `{placeholder}`s, where a placeholder is
+//! `[space.]name[8|%][:.precision]`, e.g. `{r8}`, `{h:.1}`, `{a%}` or
+//! `{rgb.b}`. `8` scales a 0..1 value to 0..255 and rounds it, `%` scales it
+//! to 0..100. `{{` and `}}` produce literal braces.
+
+/// Decimals used by default when a placeholder doesn't specify a
+/// precision, trailing zeros are trimmed.
+pub const DEFAULT_PRECISION: usize = 3;
+
+pub enum Placeholder {
+    Number(f32),
+    Text(String),
+}
+
+/// Renders `template`, resolving placeholders with `lookup(space, name)`.
+/// Unknown placeholders are kept as they were written.
+pub fn render(
+    template: &str,
+    precision: usize,
+    lookup: impl Fn(Option<&str>, &str) -> Option<Placeholder>,
+) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        output.push_str(&rest[..start]);
+        let brace = rest.as_bytes()[start];
+        rest = &rest[start + 1..];
+
+        if rest.as_bytes().first() == Some(&brace) {
+            output.push(brace as char);
+            rest = &rest[1..];
+            continue;
+        }
+
+        if brace == b'}' {
+            output.push('}');
+            continue;
+        }
+
+        let Some(end) = rest.find('}') else {
+            output.push('{');
+            continue;
+        };
+
+        let placeholder = &rest[..end];
+        match render_placeholder(placeholder, precision, &lookup) {
+            Some(value) => output.push_str(&value),
+            None => {
+                output.push('{');
+                output.push_str(placeholder);
+                output.push('}');
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn render_placeholder(
+    placeholder: &str,
+    default_precision: usize,
+    lookup: &impl Fn(Option<&str>, &str) -> Option<Placeholder>,
+) -> Option<String> {
+    let (placeholder, precision) = match placeholder.split_once(":.") {
+        Some((placeholder, precision)) => (placeholder, Some(precision.parse::<usize>().ok()?)),
+        None => (placeholder, None),
+    };
+
+    let (space, name) = match placeholder.split_once('.') {
+        Some((space, name)) => (Some(space), name),
+        None => (None, placeholder),
+    };
+
+    let (name, scale) = if let Some(name) = name.strip_suffix('8') {
+        (name, Scale::Byte)
+    } else if let Some(name) = name.strip_suffix('%') {
+        (name, Scale::Percent)
+    } else {
+        (name, Scale::None)
+    };
+
+    if name.is_empty() || !name.bytes().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    match lookup(space, name)? {
+        Placeholder::Text(text) => Some(text),
+        Placeholder::Number(value) => Some(match scale {
+            Scale::Byte => {
+                let value = (value * 255.0).round();
+                format_number(value, Some(precision.unwrap_or(0)), 0)
+            }
+            Scale::Percent => format_number(value * 100.0, precision, default_precision),
+            Scale::None => format_number(value, precision, default_precision),
+        }),
+    }
+}
+
+enum Scale {
+    None,
+    Byte,
+    Percent,
+}
+
+/// Formats with exactly `precision` decimals, or with up to
+/// `default_precision` decimals and no trailing zeros.
+pub fn format_number(value: f32, precision: Option<usize>, default_precision: usize) -> String {
+    let mut string = match precision {
+        Some(precision) => format!("{value:.precision$}"),
+        None => {
+            let string = format!("{value:.default_precision$}");
+            // Without decimals the zeros are integer digits
+            if string.contains('.') {
+                string
+                    .trim_end_matches('0')
+                    .trim_end_matches('.')
+                    .to_string()
+            } else {
+                string
+            }
+        }
+    };
+
+    if string.starts_with('-') && string[1..].bytes().all(|c| matches!(c, b'0' | b'.')) {
+        string.remove(0);
+    }
+
+    string
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_number, render, Placeholder, DEFAULT_PRECISION};
+
+    fn lookup(space: Option<&str>, name: &str) -> Option<Placeholder> {
+        match (space, name) {
+            (None, "r") => Some(Placeholder::Number(1.0)),
+            (None, "g") => Some(Placeholder::Number(0.5)),
+            (None, "h") => Some(Placeholder::Number(212.3456)),
+            (None, "a") => Some(Placeholder::Number(0.25)),
+            (Some("rgb"), "b") => Some(Placeholder::Number(-0.0001)),
+            (None, "hex") => Some(Placeholder::Text("#FF8000".into())),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(render("{r8} {g8}", DEFAULT_PRECISION, lookup), "255 128");
+        assert_eq!(render("{h:.1}", DEFAULT_PRECISION, lookup), "212.3");
+        assert_eq!(render("{h}", DEFAULT_PRECISION, lookup), "212.346");
+        assert_eq!(render("{h}", 1, lookup), "212.3");
+        assert_eq!(render("{a%}%", DEFAULT_PRECISION, lookup), "25%");
+        assert_eq!(render("{g%:.2}", DEFAULT_PRECISION, lookup), "50.00");
+        assert_eq!(render("{rgb.b}", DEFAULT_PRECISION, lookup), "0");
+        assert_eq!(render("{hex}", DEFAULT_PRECISION, lookup), "#FF8000");
+    }
+
+    #[test]
+    fn literal() {
+        assert_eq!(
+            render("Color {{ r: {r} }}", DEFAULT_PRECISION, lookup),
+            "Color { r: 1 }".to_string()
+        );
+        assert_eq!(
+            render("{unknown} {r:.x} {", DEFAULT_PRECISION, lookup),
+            "{unknown} {r:.x} {"
+        );
+        assert_eq!(render("}", DEFAULT_PRECISION, lookup), "}");
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(format_number(0.1, None, DEFAULT_PRECISION), "0.1");
+        assert_eq!(format_number(1.0, None, DEFAULT_PRECISION), "1");
+        assert_eq!(format_number(-0.0, Some(2), DEFAULT_PRECISION), "0.00");
+        assert_eq!(format_number(-0.5, None, DEFAULT_PRECISION), "-0.5");
+        assert_eq!(format_number(120.0, None, 0), "120");
+        assert_eq!(format_number(100.0, None, 0), "100");
+        assert_eq!(format_number(0.0, None, 0), "0");
+        assert_eq!(format_number(0.4, None, 0), "0");
+        assert_eq!(format_number(-0.4, None, 0), "0");
+        assert_eq!(format_number(360.04, None, 1), "360");
+    }
+}
@@ -0,0 +1,326 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Gradients between columns, interpolated like CSS Color 4 with
+//! premultiplied alpha and hue interpolation methods.
+//! https://www.w3.org/TR/css-color-4/#interpolation
+
+use crate::colorspace::{
+    cie::{linear_to_srgb, srgb_to_linear, RgbSpace},
+    gamut::in_gamut,
+    hsv_to_rgb, oklab_to_rgb, rgb_to_hsv, rgb_to_oklab,
+};
+use crate::format::format_number;
+use crate::hex;
+
+/// The most columns a gradient can use as stops
+pub const MAX_STOPS: usize = 8;
+/// Stops of the CSS export when CSS can't interpolate in the space
+const CSS_SAMPLES: usize = 17;
+/// Chroma or saturation below which the hue is powerless
+const ACHROMATIC: f32 = 1e-4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    Srgb,
+    SrgbLinear,
+    Oklab,
+    #[default]
+    Oklch,
+    Hsv,
+}
+
+impl Interpolation {
+    /// In the order of the interpolation dropdown
+    pub const ALL: [Self; 5] = [
+        Self::Srgb,
+        Self::SrgbLinear,
+        Self::Oklab,
+        Self::Oklch,
+        Self::Hsv,
+    ];
+
+    /// The `<color-space>` of CSS, `None` for spaces CSS can't interpolate in
+    pub fn css_name(self) -> Option<&'static str> {
+        match self {
+            Interpolation::Srgb => Some("srgb"),
+            Interpolation::SrgbLinear => Some("srgb-linear"),
+            Interpolation::Oklab => Some("oklab"),
+            Interpolation::Oklch => Some("oklch"),
+            Interpolation::Hsv => None,
+        }
+    }
+
+    /// The index of the hue component of polar spaces
+    pub fn hue(self) -> Option<usize> {
+        match self {
+            Interpolation::Oklch => Some(2),
+            Interpolation::Hsv => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Converts sRGB into the components of the space
+    pub fn from_srgb(self, rgb: [f32; 3]) -> [f32; 3] {
+        match self {
+            Interpolation::Srgb => rgb,
+            Interpolation::SrgbLinear => rgb.map(srgb_to_linear),
+            Interpolation::Oklab => rgb_to_oklab(rgb[0], rgb[1], rgb[2]),
+            Interpolation::Oklch => {
+                let [l, a, b] = rgb_to_oklab(rgb[0], rgb[1], rgb[2]);
+                [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+            }
+            Interpolation::Hsv => rgb_to_hsv(rgb[0], rgb[1], rgb[2]),
+        }
+    }
+
+    /// Converts the components of the space into sRGB
+    pub fn to_srgb(self, components: [f32; 3]) -> [f32; 3] {
+        let [x, y, z] = components;
+        match self {
+            Interpolation::Srgb => components,
+            Interpolation::SrgbLinear => components.map(linear_to_srgb),
+            Interpolation::Oklab => oklab_to_rgb(x, y, z),
+            Interpolation::Oklch => {
+                let (sin, cos) = z.to_radians().sin_cos();
+                oklab_to_rgb(x, y * cos, y * sin)
+            }
+            Interpolation::Hsv => hsv_to_rgb(x.rem_euclid(360.0), y, z),
+        }
+    }
+
+    fn achromatic(self, components: [f32; 3]) -> bool {
+        match self {
+            Interpolation::Oklch => components[1] < ACHROMATIC,
+            Interpolation::Hsv => components[1] < ACHROMATIC || components[2] < ACHROMATIC,
+            _ => false,
+        }
+    }
+}
+
+/// How polar spaces go around the hue circle
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HueMethod {
+    #[default]
+    Shorter,
+    Longer,
+}
+
+impl HueMethod {
+    /// In the order of the hue dropdown
+    pub const ALL: [Self; 2] = [Self::Shorter, Self::Longer];
+}
+
+/// The start and end of a segment between two stops, premultiplied
+/// components of the space followed by alpha. Hues are unwrapped, so
+/// the segment can be interpolated linearly.
+pub type Segment = [[f32; 4]; 2];
+
+/// The segments between consecutive sRGB `stops`
+pub fn segments(stops: &[[f32; 4]], interpolation: Interpolation, hue: HueMethod) -> Vec<Segment> {
+    stops
+        .windows(2)
+        .map(|pair| {
+            let [mut start, mut end] = [pair[0], pair[1]].map(|stop| {
+                let [x, y, z] = interpolation.from_srgb([stop[0], stop[1], stop[2]]);
+                [x, y, z, stop[3].clamp(0.0, 1.0)]
+            });
+
+            if let Some(h) = interpolation.hue() {
+                // A powerless hue takes the hue of the other stop
+                let achromatic = |c: [f32; 4]| interpolation.achromatic([c[0], c[1], c[2]]);
+                match (achromatic(start), achromatic(end)) {
+                    (true, false) => start[h] = end[h],
+                    (false, true) => end[h] = start[h],
+                    _ => {}
+                }
+
+                let delta = end[h] - start[h];
+                match hue {
+                    HueMethod::Shorter if delta > 180.0 => start[h] += 360.0,
+                    HueMethod::Shorter if delta < -180.0 => end[h] += 360.0,
+                    HueMethod::Longer if 0.0 < delta && delta < 180.0 => start[h] += 360.0,
+                    HueMethod::Longer if -180.0 < delta && delta <= 0.0 => end[h] += 360.0,
+                    _ => {}
+                }
+            }
+
+            for color in [&mut start, &mut end] {
+                for i in (0..3).filter(|i| Some(*i) != interpolation.hue()) {
+                    color[i] *= color[3];
+                }
+            }
+
+            [start, end]
+        })
+        .collect()
+}
+
+/// The sRGB color at `t` from 0 to 1 along `segments`
+pub fn sample(segments: &[Segment], interpolation: Interpolation, t: f32) -> [f32; 4] {
+    let Some(last) = segments.len().checked_sub(1) else {
+        return [0.0; 4];
+    };
+
+    let position = t.clamp(0.0, 1.0) * segments.len() as f32;
+    let index = (position.floor() as usize).min(last);
+    let t = position - index as f32;
+
+    let [start, end] = segments[index];
+    let mut color = [0, 1, 2, 3].map(|i| start[i] + (end[i] - start[i]) * t);
+    let alpha = color[3];
+    if alpha > 0.0 {
+        for i in (0..3).filter(|i| Some(*i) != interpolation.hue()) {
+            color[i] /= alpha;
+        }
+    }
+
+    let [r, g, b] = interpolation.to_srgb([color[0], color[1], color[2]]);
+    [r, g, b, alpha]
+}
+
+/// `count` evenly spaced sRGB colors from the first to the last stop
+pub fn samples(
+    stops: &[[f32; 4]],
+    interpolation: Interpolation,
+    hue: HueMethod,
+    count: usize,
+) -> Vec<[f32; 4]> {
+    let segments = segments(stops, interpolation, hue);
+    (0..count)
+        .map(|i| {
+            sample(
+                &segments,
+                interpolation,
+                i as f32 / (count.max(2) - 1) as f32,
+            )
+        })
+        .collect()
+}
+
+/// A left to right CSS `linear-gradient()`. Spaces CSS can't interpolate
+/// in are sampled into many stops.
+pub fn to_css(stops: &[[f32; 4]], interpolation: Interpolation, hue: HueMethod) -> String {
+    match interpolation.css_name() {
+        Some(space) => {
+            let method = match (interpolation.hue(), hue) {
+                (Some(_), HueMethod::Longer) => " longer hue",
+                _ => "",
+            };
+            let stops: Vec<String> = stops.iter().map(|stop| css_color(*stop)).collect();
+            format!(
+                "linear-gradient(to right in {space}{method}, {})",
+                stops.join(", ")
+            )
+        }
+        None => {
+            let stops: Vec<String> = samples(stops, interpolation, hue, CSS_SAMPLES)
+                .into_iter()
+                .map(css_color)
+                .collect();
+            format!("linear-gradient(to right, {})", stops.join(", "))
+        }
+    }
+}
+
+/// Hex when inside of sRGB, extended `color(srgb ...)` otherwise
+fn css_color(rgba: [f32; 4]) -> String {
+    if in_gamut([rgba[0], rgba[1], rgba[2]], RgbSpace::Srgb) {
+        return hex::to_hex(rgba);
+    }
+
+    let [r, g, b, a] = rgba.map(|c| format_number(c, None, 4));
+    if rgba[3] < 1.0 {
+        format!("color(srgb {r} {g} {b} / {a})")
+    } else {
+        format!("color(srgb {r} {g} {b})")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{sample, samples, segments, to_css, HueMethod, Interpolation};
+
+    const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+    const BLUE: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+
+    #[test]
+    fn endpoints() {
+        let stops = [RED, [0.2, 0.8, 0.4, 0.5], BLUE];
+        for interpolation in Interpolation::ALL {
+            let colors = samples(&stops, interpolation, HueMethod::Shorter, 5);
+            assert!(aprox_eq(colors[0], RED), "{interpolation:?}");
+            assert!(aprox_eq(colors[2], stops[1]), "{interpolation:?}");
+            assert!(aprox_eq(colors[4], BLUE), "{interpolation:?}");
+        }
+    }
+
+    #[test]
+    fn midpoints() {
+        let black_white = [[0.0, 0.0, 0.0, 1.0], [1.0; 4]];
+        let middle = |interpolation| samples(&black_white, interpolation, HueMethod::Shorter, 3)[1];
+        assert!(aprox_eq(middle(Interpolation::Srgb), [0.5, 0.5, 0.5, 1.0]));
+        assert!(aprox_eq(
+            middle(Interpolation::SrgbLinear),
+            [0.7354, 0.7354, 0.7354, 1.0]
+        ));
+    }
+
+    #[test]
+    fn hue_methods() {
+        // Red to blue through magenta, or the other way through green
+        let shorter = samples(&[RED, BLUE], Interpolation::Oklch, HueMethod::Shorter, 3)[1];
+        let longer = samples(&[RED, BLUE], Interpolation::Oklch, HueMethod::Longer, 3)[1];
+        assert!(shorter[0] > shorter[1] && shorter[2] > shorter[1]);
+        assert!(longer[1] > longer[0] && longer[1] > longer[2]);
+
+        // Equal hues go all the way around with longer
+        let segment = segments(&[RED, RED], Interpolation::Hsv, HueMethod::Longer)[0];
+        assert_eq!(segment[1][0] - segment[0][0], 360.0);
+    }
+
+    #[test]
+    fn powerless_hue() {
+        // Gray takes the hue of blue instead of going through red
+        let gray = [0.5, 0.5, 0.5, 1.0];
+        let segments = segments(&[gray, BLUE], Interpolation::Hsv, HueMethod::Shorter);
+        let middle = sample(&segments, Interpolation::Hsv, 0.5);
+        assert!(middle[0] == middle[1] && middle[2] > middle[0]);
+    }
+
+    #[test]
+    fn premultiplied() {
+        let transparent = [1.0, 0.0, 0.0, 0.0];
+        let middle = samples(
+            &[transparent, BLUE],
+            Interpolation::Srgb,
+            HueMethod::Shorter,
+            3,
+        )[1];
+        assert!(aprox_eq(middle, [0.0, 0.0, 1.0, 0.5]));
+    }
+
+    #[test]
+    fn css() {
+        assert_eq!(
+            to_css(&[RED, BLUE], Interpolation::Oklch, HueMethod::Longer),
+            "linear-gradient(to right in oklch longer hue, #FF0000, #0000FF)"
+        );
+        assert_eq!(
+            to_css(
+                &[RED, [1.2, 0.0, 0.0, 0.5]],
+                Interpolation::Srgb,
+                HueMethod::Longer
+            ),
+            "linear-gradient(to right in srgb, #FF0000, color(srgb 1.2 0 0 / 0.5))"
+        );
+
+        let hsv = to_css(&[RED, BLUE], Interpolation::Hsv, HueMethod::Shorter);
+        assert_eq!(hsv.matches('#').count(), 17);
+    }
+
+    fn aprox_eq(a: [f32; 4], b: [f32; 4]) -> bool {
+        const EPSILON: f32 = 1e-3;
+
+        (0..4).all(|i| a[i] > b[i] - EPSILON && a[i] < b[i] + EPSILON)
+    }
+}
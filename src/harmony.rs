@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Color harmonies, rotating the OKLCh hue so every color keeps the
+//! perceived lightness and chroma of the base color.
+
+use crate::colorspace::{oklab_to_rgb, rgb_to_oklab};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Harmony {
+    #[default]
+    Complementary,
+    Analogous,
+    Triadic,
+    SplitComplementary,
+    /// Two complementary pairs, `spread` apart
+    Tetradic,
+    Square,
+}
+
+impl Harmony {
+    /// In the order of the harmony dropdown
+    pub const ALL: [Self; 6] = [
+        Self::Complementary,
+        Self::Analogous,
+        Self::Triadic,
+        Self::SplitComplementary,
+        Self::Tetradic,
+        Self::Square,
+    ];
+
+    /// The default angle spread in degrees, `None` if the rule has none
+    pub fn default_spread(self) -> Option<f32> {
+        match self {
+            Harmony::Complementary | Harmony::Square => None,
+            Harmony::Analogous | Harmony::SplitComplementary => Some(30.0),
+            Harmony::Triadic | Harmony::Tetradic => Some(60.0),
+        }
+    }
+
+    /// The hue rotations in degrees of the generated colors, the base color
+    /// not included
+    pub fn rotations(self, spread: f32) -> Vec<f32> {
+        match self {
+            Harmony::Complementary => vec![180.0],
+            Harmony::Analogous => vec![-spread, spread],
+            // Triadic is split complementary with a wider default
+            Harmony::Triadic | Harmony::SplitComplementary => {
+                vec![180.0 - spread, 180.0 + spread]
+            }
+            Harmony::Tetradic => vec![spread, 180.0, 180.0 + spread],
+            Harmony::Square => vec![90.0, 180.0, 270.0],
+        }
+    }
+}
+
+/// Rotates the OKLCh hue of sRGB `rgb` by `degrees`, keeping alpha
+pub fn rotate_hue(rgb: [f32; 4], degrees: f32) -> [f32; 4] {
+    let [l, a, b] = rgb_to_oklab(rgb[0], rgb[1], rgb[2]);
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let [r, g, b] = oklab_to_rgb(l, a * cos - b * sin, a * sin + b * cos);
+    [r, g, b, rgb[3]]
+}
+
+/// The colors `harmony` adds to sRGB `rgb`
+pub fn generate(rgb: [f32; 4], harmony: Harmony, spread: f32) -> Vec<[f32; 4]> {
+    harmony
+        .rotations(spread)
+        .into_iter()
+        .map(|degrees| rotate_hue(rgb, degrees))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, rotate_hue, Harmony};
+    use crate::colorspace::rgb_to_oklab;
+
+    fn lch(rgb: [f32; 4]) -> [f32; 3] {
+        let [l, a, b] = rgb_to_oklab(rgb[0], rgb[1], rgb[2]);
+        [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+    }
+
+    #[test]
+    fn keeps_lightness_and_chroma() {
+        let base = [0.8, 0.3, 0.2, 0.5];
+        let [l, c, h] = lch(base);
+
+        for harmony in Harmony::ALL {
+            let spread = harmony.default_spread().unwrap_or(0.0);
+            for (color, degrees) in generate(base, harmony, spread)
+                .into_iter()
+                .zip(harmony.rotations(spread))
+            {
+                let [l2, c2, h2] = lch(color);
+                assert!(aprox_eq(l, l2) && aprox_eq(c, c2), "{harmony:?}");
+                assert!(aprox_eq((h + degrees).rem_euclid(360.0), h2), "{harmony:?}");
+                assert_eq!(color[3], 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn rotations() {
+        assert_eq!(Harmony::Complementary.rotations(0.0), [180.0]);
+        assert_eq!(Harmony::Analogous.rotations(30.0), [-30.0, 30.0]);
+        assert_eq!(Harmony::Triadic.rotations(60.0), [120.0, 240.0]);
+        assert_eq!(Harmony::SplitComplementary.rotations(30.0), [150.0, 210.0]);
+        assert_eq!(Harmony::Tetradic.rotations(60.0), [60.0, 180.0, 240.0]);
+        assert_eq!(Harmony::Square.rotations(0.0).len(), 3);
+    }
+
+    #[test]
+    fn full_turn() {
+        let base = [0.2, 0.4, 0.6, 1.0];
+        let turned = rotate_hue(base, 360.0);
+        for i in 0..3 {
+            assert!(aprox_eq(base[i], turned[i]));
+        }
+    }
+
+    fn aprox_eq(a: f32, b: f32) -> bool {
+        const EPSILON: f32 = 1e-3;
+
+        a > b - EPSILON && a < b + EPSILON
+    }
+}
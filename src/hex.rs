@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+/// Parses `#RGB`, `#RGBA`, `#RRGGBB` and `#RRGGBBAA` (the `#` is optional)
+/// into normalized RGBA.
+pub fn parse_hex(string: &str) -> Option<[f32; 4]> {
+    let string = string.trim();
+    let digits = string.strip_prefix('#').unwrap_or(string);
+    if !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let nibble = |i: usize| u8::from_str_radix(&digits[i..i + 1], 16).ok();
+    let byte = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+
+    let rgba = match digits.len() {
+        3 | 4 => {
+            let mut rgba = [255u8; 4];
+            for (i, channel) in rgba.iter_mut().take(digits.len()).enumerate() {
+                *channel = nibble(i)? * 0x11;
+            }
+            rgba
+        }
+        6 | 8 => {
+            let mut rgba = [255u8; 4];
+            for (i, channel) in rgba.iter_mut().take(digits.len() / 2).enumerate() {
+                *channel = byte(i * 2)?;
+            }
+            rgba
+        }
+        _ => return None,
+    };
+
+    Some(rgba.map(|c| c as f32 / 255.0))
+}
+
+/// Formats RGBA as `#RRGGBB`, or `#RRGGBBAA` when the color isn't opaque.
+/// Out of range components are clamped.
+pub fn to_hex(rgba: [f32; 4]) -> String {
+    let [r, g, b, a] = rgba.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+
+    if a == 255 {
+        format!("#{r:02X}{g:02X}{b:02X}")
+    } else {
+        format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_hex, to_hex};
+
+    #[test]
+    fn short() {
+        assert_eq!(parse_hex("#f00"), Some([1.0, 0.0, 0.0, 1.0]));
+        assert_eq!(
+            parse_hex("0f08"),
+            Some([0.0, 1.0, 0.0, 0x88 as f32 / 255.0])
+        );
+    }
+
+    #[test]
+    fn long() {
+        assert_eq!(parse_hex("#0000FF"), Some([0.0, 0.0, 1.0, 1.0]));
+        assert_eq!(parse_hex(" #ffffff00 "), Some([1.0, 1.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(parse_hex(""), None);
+        assert_eq!(parse_hex("#"), None);
+        assert_eq!(parse_hex("#12345"), None);
+        assert_eq!(parse_hex("#ggg"), None);
+        assert_eq!(parse_hex("#+ff"), None);
+        assert_eq!(parse_hex("#+fffff"), None);
+        assert_eq!(parse_hex("#ééé"), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        for hex in ["#000000", "#FFFFFF", "#12AB9C", "#12AB9C80"] {
+            assert_eq!(to_hex(parse_hex(hex).unwrap()), hex);
+        }
+    }
+
+    #[test]
+    fn clamped() {
+        assert_eq!(to_hex([1.5, -0.5, 0.5, 1.0]), "#FF0080");
+    }
+}
@@ -1,13 +1,32 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use app::ColorPicker;
+use app::{ColorPicker, Flags};
+use config::Config;
+mod apca;
 mod app;
 mod colorspace;
+mod config;
+mod contrast;
 mod core;
+mod css;
+mod cvd;
+mod delta_e;
+mod format;
+mod gradient;
+mod harmony;
+mod hex;
+mod scale;
 mod shaders;
+mod wheel;
 mod widgets;
 
 fn main() -> cosmic::iced::Result {
+    let (config_handler, config) = Config::load();
+    let flags = Flags {
+        config_handler,
+        config,
+    };
+
     let settings = cosmic::app::Settings::default();
-    cosmic::app::run::<ColorPicker>(settings, ())
+    cosmic::app::run::<ColorPicker>(settings, flags)
 }
@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Tint and shade scales, like the 50–950 ramps of design tokens, keeping
+//! the hue of the base color.
+
+use crate::colorspace::{
+    cie::{Illuminant, RgbSpace},
+    gamut::map_into_gamut,
+    lab_to_rgb, oklab_to_rgb, rgb_to_lab, rgb_to_oklab,
+};
+use crate::hex;
+
+/// The lightness of the lightest and darkest OKLCh step
+const OKLCH_LIGHTNESS: (f32, f32) = (0.97, 0.25);
+/// The lightness of the lightest and darkest CIELAB step
+const LAB_LIGHTNESS: (f32, f32) = (97.0, 15.0);
+
+/// The space the lightness is varied in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScaleSpace {
+    #[default]
+    Oklch,
+    /// CIE LCh relative to D50
+    Lch,
+}
+
+impl ScaleSpace {
+    /// In the order of the space dropdown
+    pub const ALL: [Self; 2] = [Self::Oklch, Self::Lch];
+}
+
+/// How the lightness is spaced from the lightest to the darkest step
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Dense light steps
+    EaseIn,
+    /// Dense dark steps
+    EaseOut,
+    /// Dense steps at both ends
+    EaseInOut,
+}
+
+impl Easing {
+    /// In the order of the easing dropdown
+    pub const ALL: [Self; 4] = [Self::Linear, Self::EaseIn, Self::EaseOut, Self::EaseInOut];
+
+    /// Eases `t` from 0 to 1
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scale {
+    pub steps: usize,
+    pub space: ScaleSpace,
+    pub easing: Easing,
+    /// Reduce the chroma of steps lighter or darker than the base color
+    pub vary_chroma: bool,
+}
+
+impl Scale {
+    /// The steps from lightest to darkest, mapped into sRGB. Alpha is kept.
+    pub fn generate(&self, rgb: [f32; 4]) -> Vec<[f32; 4]> {
+        let srgb = [rgb[0], rgb[1], rgb[2]];
+        let ([l, a, b], (lightest, darkest)) = match self.space {
+            ScaleSpace::Oklch => (rgb_to_oklab(srgb[0], srgb[1], srgb[2]), OKLCH_LIGHTNESS),
+            ScaleSpace::Lch => (rgb_to_lab(srgb, Illuminant::D50), LAB_LIGHTNESS),
+        };
+
+        (0..self.steps)
+            .map(|step| {
+                let t = step as f32 / (self.steps.max(2) - 1) as f32;
+                let lightness = lightest + (darkest - lightest) * self.easing.apply(t);
+
+                // Full chroma at the base lightness, none at the ends
+                let scale = if self.vary_chroma {
+                    let end = if lightness > l { lightest } else { darkest };
+                    let distance = ((lightness - l) / (end - l)).clamp(0.0, 1.0);
+                    1.0 - distance * distance
+                } else {
+                    1.0
+                };
+
+                let step = match self.space {
+                    ScaleSpace::Oklch => oklab_to_rgb(lightness, a * scale, b * scale),
+                    ScaleSpace::Lch => {
+                        lab_to_rgb([lightness, a * scale, b * scale], Illuminant::D50)
+                    }
+                };
+                let [r, g, b] = map_into_gamut(step, RgbSpace::Srgb);
+                [r, g, b, rgb[3]]
+            })
+            .collect()
+    }
+}
+
+/// The token names of `steps` steps, 50 to 950 for 11 steps and multiples
+/// of 100 otherwise
+pub fn step_names(steps: usize) -> Vec<String> {
+    if steps == 11 {
+        std::iter::once(50)
+            .chain((1..=9).map(|step| step * 100))
+            .chain(std::iter::once(950))
+            .map(|name| name.to_string())
+            .collect()
+    } else {
+        (1..=steps).map(|step| (step * 100).to_string()).collect()
+    }
+}
+
+/// A text format for the exported palette
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PaletteFormat {
+    /// CSS custom properties, `--name-500: #RRGGBB;`
+    #[default]
+    Css,
+    /// A JSON object of names to hex colors
+    Json,
+    /// A GIMP palette, also read by Inkscape and Krita
+    Gpl,
+}
+
+impl PaletteFormat {
+    /// In the order of the export dropdown
+    pub const ALL: [Self; 3] = [Self::Css, Self::Json, Self::Gpl];
+
+    pub fn export(self, name: &str, steps: &[String], colors: &[[f32; 4]]) -> String {
+        let entries = steps.iter().zip(colors);
+        match self {
+            PaletteFormat::Css => entries
+                .map(|(step, color)| format!("--{name}-{step}: {};\n", hex::to_hex(*color)))
+                .collect(),
+            PaletteFormat::Json => {
+                let entries: Vec<String> = entries
+                    .map(|(step, color)| format!("  \"{step}\": \"{}\"", hex::to_hex(*color)))
+                    .collect();
+                format!("{{\n{}\n}}\n", entries.join(",\n"))
+            }
+            PaletteFormat::Gpl => {
+                let mut palette = format!("GIMP Palette\nName: {name}\nColumns: 0\n#\n");
+                for (step, color) in entries {
+                    let [r, g, b] = [0, 1, 2].map(|i| (color[i].clamp(0.0, 1.0) * 255.0).round());
+                    palette += &format!("{r:3} {g:3} {b:3}\t{name}-{step}\n");
+                }
+                palette
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{step_names, Easing, PaletteFormat, Scale, ScaleSpace};
+    use crate::colorspace::{
+        cie::{Illuminant, RgbSpace},
+        gamut::in_gamut,
+        rgb_to_lab, rgb_to_oklab,
+    };
+
+    const SCALE: Scale = Scale {
+        steps: 11,
+        space: ScaleSpace::Oklch,
+        easing: Easing::Linear,
+        vary_chroma: false,
+    };
+
+    #[test]
+    fn lightness() {
+        let base = [0.2, 0.4, 0.8, 1.0];
+        for space in ScaleSpace::ALL {
+            let colors = Scale { space, ..SCALE }.generate(base);
+            assert_eq!(colors.len(), 11);
+
+            let lightness: Vec<f32> = colors
+                .iter()
+                .map(|c| match space {
+                    ScaleSpace::Oklch => rgb_to_oklab(c[0], c[1], c[2])[0],
+                    ScaleSpace::Lch => rgb_to_lab([c[0], c[1], c[2]], Illuminant::D50)[0],
+                })
+                .collect();
+            assert!(lightness.windows(2).all(|pair| pair[0] > pair[1]));
+            assert!(colors
+                .iter()
+                .all(|c| in_gamut([c[0], c[1], c[2]], RgbSpace::Srgb)));
+        }
+    }
+
+    #[test]
+    fn easing() {
+        for easing in Easing::ALL {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn chroma() {
+        // The ends lose their chroma, the alpha is kept
+        let base = [0.9, 0.3, 0.1, 0.5];
+        let colors = Scale {
+            vary_chroma: true,
+            ..SCALE
+        }
+        .generate(base);
+        let [_, a, b] = rgb_to_oklab(colors[0][0], colors[0][1], colors[0][2]);
+        assert!(a.hypot(b) < 1e-3);
+        assert!(colors.iter().all(|c| c[3] == 0.5));
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(step_names(11)[0], "50");
+        assert_eq!(step_names(11)[10], "950");
+        assert_eq!(
+            step_names(9),
+            ["100", "200", "300", "400", "500", "600", "700", "800", "900"]
+        );
+    }
+
+    #[test]
+    fn export() {
+        let steps = step_names(2);
+        let colors = [[1.0, 1.0, 1.0, 1.0], [0.0, 0.0, 0.0, 1.0]];
+        assert_eq!(
+            PaletteFormat::Css.export("gray", &steps, &colors),
+            "--gray-100: #FFFFFF;\n--gray-200: #000000;\n"
+        );
+        assert_eq!(
+            PaletteFormat::Json.export("gray", &steps, &colors),
+            "{\n  \"100\": \"#FFFFFF\",\n  \"200\": \"#000000\"\n}\n"
+        );
+        assert!(PaletteFormat::Gpl
+            .export("gray", &steps, &colors)
+            .ends_with("255 255 255\tgray-100\n  0   0   0\tgray-200\n"));
+    }
+}
@@ -0,0 +1,113 @@
+struct Chromaticity {
+    x: f32,
+    y: f32,
+}
+
+@group(0) @binding(0) var<uniform> chromaticity: Chromaticity;
+
+// The visible area of the diagram
+const X_MAX = 0.8;
+const Y_MAX = 0.9;
+
+// CIE 1931 2° spectral locus from 380nm to 700nm in 5nm steps
+const LOCUS_LEN = 65u;
+const LOCUS = array<vec2<f32>, 65>(
+    vec2<f32>(0.1741, 0.0050), vec2<f32>(0.1740, 0.0050), vec2<f32>(0.1738, 0.0049),
+    vec2<f32>(0.1736, 0.0049), vec2<f32>(0.1733, 0.0048), vec2<f32>(0.1730, 0.0048),
+    vec2<f32>(0.1726, 0.0048), vec2<f32>(0.1721, 0.0048), vec2<f32>(0.1714, 0.0051),
+    vec2<f32>(0.1703, 0.0058), vec2<f32>(0.1689, 0.0069), vec2<f32>(0.1669, 0.0086),
+    vec2<f32>(0.1644, 0.0109), vec2<f32>(0.1611, 0.0138), vec2<f32>(0.1566, 0.0177),
+    vec2<f32>(0.1510, 0.0227), vec2<f32>(0.1440, 0.0297), vec2<f32>(0.1355, 0.0399),
+    vec2<f32>(0.1241, 0.0578), vec2<f32>(0.1096, 0.0868), vec2<f32>(0.0913, 0.1327),
+    vec2<f32>(0.0687, 0.2007), vec2<f32>(0.0454, 0.2950), vec2<f32>(0.0235, 0.4127),
+    vec2<f32>(0.0082, 0.5384), vec2<f32>(0.0039, 0.6548), vec2<f32>(0.0139, 0.7502),
+    vec2<f32>(0.0389, 0.8120), vec2<f32>(0.0743, 0.8338), vec2<f32>(0.1142, 0.8262),
+    vec2<f32>(0.1547, 0.8059), vec2<f32>(0.1929, 0.7816), vec2<f32>(0.2296, 0.7543),
+    vec2<f32>(0.2658, 0.7243), vec2<f32>(0.3016, 0.6923), vec2<f32>(0.3373, 0.6589),
+    vec2<f32>(0.3731, 0.6245), vec2<f32>(0.4087, 0.5896), vec2<f32>(0.4441, 0.5547),
+    vec2<f32>(0.4788, 0.5202), vec2<f32>(0.5125, 0.4866), vec2<f32>(0.5448, 0.4544),
+    vec2<f32>(0.5752, 0.4242), vec2<f32>(0.6029, 0.3965), vec2<f32>(0.6270, 0.3725),
+    vec2<f32>(0.6482, 0.3514), vec2<f32>(0.6658, 0.3340), vec2<f32>(0.6801, 0.3197),
+    vec2<f32>(0.6915, 0.3083), vec2<f32>(0.7006, 0.2993), vec2<f32>(0.7079, 0.2920),
+    vec2<f32>(0.7140, 0.2859), vec2<f32>(0.7190, 0.2809), vec2<f32>(0.7230, 0.2770),
+    vec2<f32>(0.7260, 0.2740), vec2<f32>(0.7283, 0.2717), vec2<f32>(0.7300, 0.2700),
+    vec2<f32>(0.7311, 0.2689), vec2<f32>(0.7320, 0.2680), vec2<f32>(0.7327, 0.2673),
+    vec2<f32>(0.7334, 0.2666), vec2<f32>(0.7340, 0.2660), vec2<f32>(0.7344, 0.2656),
+    vec2<f32>(0.7346, 0.2654), vec2<f32>(0.7347, 0.2653),
+);
+
+// sRGB primaries
+const RED = vec2<f32>(0.64, 0.33);
+const GREEN = vec2<f32>(0.30, 0.60);
+const BLUE = vec2<f32>(0.15, 0.06);
+
+@fragment
+fn fs_main(
+    @builtin(position) _clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+) -> @location(0) vec4<f32> {
+    // uv.x = x
+    // uv.y = y
+    let xy = uv * vec2<f32>(X_MAX, Y_MAX);
+    // The size of a pixel in xy
+    let pixel = length(fwidth(xy));
+
+    var color = vec4<f32>(0.0);
+    if inside_locus(xy) {
+        color = vec4<f32>(simulate_linear(xy_to_rgb(xy)), 1.0);
+    }
+
+    // sRGB triangle
+    let edge = min(
+        segment_distance(xy, RED, GREEN),
+        min(segment_distance(xy, GREEN, BLUE), segment_distance(xy, BLUE, RED)),
+    );
+    if edge < pixel {
+        color = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    // Marker, a white ring with a black outline
+    let marker = abs(distance(xy, vec2<f32>(chromaticity.x, chromaticity.y)) - pixel * 4.0);
+    if marker < pixel * 1.5 {
+        color = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    if marker < pixel * 0.75 {
+        color = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+    }
+
+    return color;
+}
+
+// Even-odd test against the locus, closed by the line of purples
+fn inside_locus(p: vec2<f32>) -> bool {
+    var inside = false;
+    var j = LOCUS_LEN - 1u;
+    for (var i = 0u; i < LOCUS_LEN; i++) {
+        let a = LOCUS[i];
+        let b = LOCUS[j];
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+        j = i;
+    }
+    return inside;
+}
+
+fn segment_distance(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+    let ab = b - a;
+    let t = clamp(dot(p - a, ab) / dot(ab, ab), 0.0, 1.0);
+    return distance(p, a + ab * t);
+}
+
+// Returns linear sRGB at the brightest luminance that fits, colors outside
+// of sRGB are clipped
+fn xy_to_rgb(xy: vec2<f32>) -> vec3<f32> {
+    let xyz = vec3<f32>(xy.x / xy.y, 1.0, (1.0 - xy.x - xy.y) / xy.y);
+    let rgb = max(vec3<f32>(
+        dot(vec3<f32>(3.2409699, -1.5373832, -0.4986108), xyz),
+        dot(vec3<f32>(-0.9692436, 1.8759675, 0.0415551), xyz),
+        dot(vec3<f32>(0.0556301, -0.2039770, 1.0569715), xyz),
+    ), vec3<f32>(0.0));
+
+    return rgb / max(rgb.x, max(rgb.y, rgb.z));
+}
@@ -0,0 +1,19 @@
+/// Draws a crosshair centered on `marker` over `color`, both in uv
+fn crosshair(color: vec4<f32>, uv: vec2<f32>, marker: vec2<f32>) -> vec4<f32> {
+    // Distances in pixels
+    let distance = abs(uv - marker) / max(fwidth(uv), vec2<f32>(1e-6));
+    let arm = max(distance.x, distance.y);
+    let line = min(distance.x, distance.y);
+
+    // A white line with a dark outline, leaving the picked color visible
+    if arm > 3.0 && arm < 10.0 {
+        if line < 1.0 {
+            return vec4<f32>(1.0);
+        } else if line < 2.0 {
+            return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        }
+    }
+
+    return color;
+}
+
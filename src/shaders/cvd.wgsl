@@ -0,0 +1,17 @@
+// Color vision deficiency simulation, prepended to the graph shaders.
+// Identity with normal vision.
+@group(0) @binding(1) var<uniform> cvd: mat3x3<f32>;
+
+fn simulate_linear(rgb: vec3<f32>) -> vec3<f32> {
+    return cvd * rgb;
+}
+
+fn simulate_srgb(rgb: vec3<f32>) -> vec3<f32> {
+    let cutoff = rgb <= vec3<f32>(0.04045);
+    let linear = select(pow((rgb + 0.055) / 1.055, vec3<f32>(2.4)), rgb / 12.92, cutoff);
+
+    let simulated = simulate_linear(linear);
+    let low = simulated <= vec3<f32>(0.0031308);
+    let gamma = 1.055 * pow(max(simulated, vec3<f32>(0.0)), vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(gamma, simulated * 12.92, low);
+}
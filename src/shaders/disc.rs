@@ -0,0 +1,139 @@
+use cosmic::{
+    iced::{event, mouse, wgpu, Rectangle},
+    iced_core::Shell,
+    iced_wgpu::graphics::Viewport,
+    iced_widget::shader::{self, Storage},
+};
+
+use crate::{
+    cvd::Simulation,
+    shaders::{graph_shader, Cvd, Picking, ShaderPipeline},
+    wheel,
+};
+
+// ---- Shader ----
+/// OKLCh chroma and hue at a fixed lightness, chroma growing outward
+pub struct ChromaDisc<'a, Message> {
+    pub lightness: f32,
+    pub chroma: f32,
+    pub hue: f32,
+    /// The chroma at the edge
+    pub max_chroma: f32,
+    /// Simulated color vision deficiency
+    pub cvd: Option<Simulation>,
+    /// Called with the picked chroma and hue
+    pub on_change: Box<dyn Fn([f32; 2]) -> Message + 'a>,
+}
+
+impl<'a, Message> shader::Program<Message> for ChromaDisc<'a, Message> {
+    type State = Picking;
+    type Primitive = Primitive;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: shader::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        _shell: &mut Shell<'_, Message>,
+    ) -> (event::Status, Option<Message>) {
+        let (status, uv) = state.update(&event, bounds, cursor);
+        let message = uv.map(|[x, y]| {
+            let position = [x * 2.0 - 1.0, y * 2.0 - 1.0];
+            (self.on_change)(wheel::disc_chroma_hue(position, self.max_chroma))
+        });
+        (status, message)
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        state.mouse_interaction(bounds, cursor)
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        _cursor: mouse::Cursor,
+        _bounds: Rectangle,
+    ) -> Self::Primitive {
+        Primitive::new(
+            self.lightness,
+            self.chroma,
+            self.hue,
+            self.max_chroma,
+            self.cvd,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Primitive {
+    uniforms: Uniforms,
+    cvd: Cvd,
+}
+
+impl Primitive {
+    pub fn new(
+        lightness: f32,
+        chroma: f32,
+        hue: f32,
+        max_chroma: f32,
+        cvd: Option<Simulation>,
+    ) -> Self {
+        Self {
+            uniforms: Uniforms {
+                lightness,
+                max_chroma,
+                marker: wheel::disc_position(chroma, hue, max_chroma),
+            },
+            cvd: Cvd::new(cvd),
+        }
+    }
+}
+
+impl shader::Primitive for Primitive {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        storage: &mut Storage,
+        bounds: &Rectangle,
+        viewport: &Viewport,
+    ) {
+        if !storage.has::<ShaderPipeline<Uniforms, 0>>() {
+            storage.store(ShaderPipeline::<Uniforms, 0>::new(
+                device,
+                queue,
+                format,
+                graph_shader!("disc.wgsl"),
+            ));
+        }
+
+        let pipeline = storage.get_mut::<ShaderPipeline<Uniforms, 0>>().unwrap();
+        pipeline.write(queue, &self.uniforms, &self.cvd);
+    }
+
+    fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        storage: &Storage,
+        target: &wgpu::TextureView,
+        clip_bounds: &Rectangle<u32>,
+    ) {
+        let pipeline = storage.get::<ShaderPipeline<Uniforms, 0>>().unwrap();
+        pipeline.render(target, encoder, clip_bounds);
+    }
+}
+
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Uniforms {
+    lightness: f32,
+    max_chroma: f32,
+    marker: [f32; 2],
+}
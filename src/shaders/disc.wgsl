@@ -0,0 +1,61 @@
+struct Disc {
+    lightness: f32,
+    max_chroma: f32,
+    marker: vec2<f32>,
+}
+
+@group(0) @binding(0) var<uniform> disc: Disc;
+
+@fragment
+fn fs_main(
+    @builtin(position) _clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+) -> @location(0) vec4<f32> {
+    // Centered, from -1 to 1 with y going up
+    let position = uv * 2.0 - 1.0;
+    let pixel = fwidth(position.x);
+    let distance = length(position);
+
+    // Chroma grows outward, the hue goes around
+    let chroma = distance * disc.max_chroma;
+    let hue = atan2(position.y, position.x);
+    let rgb = oklab_to_linear(disc.lightness, chroma * cos(hue), chroma * sin(hue));
+
+    var color = vec4<f32>(rgb, 1.0);
+    if max(color.x, max(color.y, color.z)) > 1.0 || min(color.x, min(color.y, color.z)) < 0.0 {
+        color.w = 0.1;
+    }
+    color.w *= 1.0 - smoothstep(1.0 - pixel, 1.0, distance);
+    color = vec4<f32>(simulate_linear(color.xyz), color.w);
+
+    return circle_marker(color, position, disc.marker, pixel);
+}
+
+/// A white ring with dark outlines around `marker`
+fn circle_marker(color: vec4<f32>, position: vec2<f32>, marker: vec2<f32>, pixel: f32) -> vec4<f32> {
+    let distance = length(position - marker) / pixel;
+    if distance > 4.0 && distance < 8.0 {
+        if distance > 5.0 && distance < 7.0 {
+            return vec4<f32>(1.0);
+        }
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    return color;
+}
+
+fn oklab_to_linear(okl: f32, a: f32, b: f32) -> vec3<f32> {
+    let l_ = okl + 0.3963377774 * a + 0.2158037573 * b;
+    let m_ = okl - 0.1055613458 * a - 0.0638541728 * b;
+    let s_ = okl - 0.0894841775 * a - 1.2914855480 * b;
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s = s_ * s_ * s_;
+
+    return vec3<f32>(
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    );
+}
@@ -0,0 +1,118 @@
+use cosmic::{
+    iced::{wgpu, Rectangle},
+    iced_wgpu::graphics::Viewport,
+    iced_widget::shader::{self, Storage},
+};
+
+use crate::{
+    cvd::Simulation,
+    gradient::{Interpolation, Segment, MAX_STOPS},
+    shaders::{graph_shader, Cvd, ShaderPipeline},
+};
+
+const MAX_SEGMENTS: usize = MAX_STOPS - 1;
+
+// ---- Shader ----
+/// A left to right gradient, interpolating `segments` in `interpolation`
+pub struct ColorGraph {
+    pub segments: Vec<Segment>,
+    pub interpolation: Interpolation,
+    /// Simulated color vision deficiency
+    pub cvd: Option<Simulation>,
+}
+
+impl<Message> shader::Program<Message> for ColorGraph {
+    type State = ();
+    type Primitive = Primitive;
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        _cursor: cosmic::iced_core::mouse::Cursor,
+        _bounds: cosmic::iced::Rectangle,
+    ) -> Self::Primitive {
+        Primitive::new(&self.segments, self.interpolation, self.cvd)
+    }
+}
+
+#[derive(Debug)]
+pub struct Primitive {
+    uniforms: Uniforms,
+    cvd: Cvd,
+}
+
+impl Primitive {
+    pub fn new(
+        segments: &[Segment],
+        interpolation: Interpolation,
+        cvd: Option<Simulation>,
+    ) -> Self {
+        let segments = &segments[..segments.len().min(MAX_SEGMENTS)];
+        let mut uniforms = Uniforms {
+            segments: [[0.0; 4]; MAX_SEGMENTS * 2],
+            count: segments.len() as u32,
+            space: match interpolation {
+                Interpolation::Srgb => 0,
+                Interpolation::SrgbLinear => 1,
+                Interpolation::Oklab => 2,
+                Interpolation::Oklch => 3,
+                Interpolation::Hsv => 4,
+            },
+            _padding: [0; 2],
+        };
+        for (index, [start, end]) in segments.iter().enumerate() {
+            uniforms.segments[index * 2] = *start;
+            uniforms.segments[index * 2 + 1] = *end;
+        }
+
+        Self {
+            uniforms,
+            cvd: Cvd::new(cvd),
+        }
+    }
+}
+
+impl shader::Primitive for Primitive {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        storage: &mut Storage,
+        bounds: &Rectangle,
+        viewport: &Viewport,
+    ) {
+        if !storage.has::<ShaderPipeline<Uniforms, 0>>() {
+            storage.store(ShaderPipeline::<Uniforms, 0>::new(
+                device,
+                queue,
+                format,
+                graph_shader!("gradient.wgsl"),
+            ));
+        }
+
+        let pipeline = storage.get_mut::<ShaderPipeline<Uniforms, 0>>().unwrap();
+        pipeline.write(queue, &self.uniforms, &self.cvd);
+    }
+
+    fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        storage: &Storage,
+        target: &wgpu::TextureView,
+        clip_bounds: &Rectangle<u32>,
+    ) {
+        let pipeline = storage.get::<ShaderPipeline<Uniforms, 0>>().unwrap();
+        pipeline.render(target, encoder, clip_bounds);
+    }
+}
+
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Uniforms {
+    /// The start and end of each segment
+    segments: [[f32; 4]; MAX_SEGMENTS * 2],
+    count: u32,
+    space: u32,
+    _padding: [u32; 2],
+}
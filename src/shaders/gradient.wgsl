@@ -0,0 +1,100 @@
+struct Gradient {
+    // The start and end of each segment, premultiplied except for hue
+    segments: array<vec4<f32>, 14>,
+    count: u32,
+    space: u32,
+    _padding: vec2<u32>,
+}
+
+const SPACE_SRGB = 0u;
+const SPACE_SRGB_LINEAR = 1u;
+const SPACE_OKLAB = 2u;
+const SPACE_OKLCH = 3u;
+const SPACE_HSV = 4u;
+
+@group(0) @binding(0) var<uniform> gradient: Gradient;
+
+@fragment
+fn fs_main(
+    @builtin(position) _clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+) -> @location(0) vec4<f32> {
+    // uv.x = position along the gradient
+    if gradient.count == 0u {
+        return vec4<f32>(0.0);
+    }
+
+    let position = clamp(uv.x, 0.0, 1.0) * f32(gradient.count);
+    let index = min(u32(floor(position)), gradient.count - 1u);
+    let t = position - f32(index);
+    let mixed = mix(gradient.segments[index * 2u], gradient.segments[index * 2u + 1u], t);
+
+    let alpha = mixed.w;
+    var c = mixed.xyz;
+    if alpha > 0.0 {
+        let unpremultiplied = c / alpha;
+        switch gradient.space {
+            case SPACE_OKLCH: {
+                c = vec3<f32>(unpremultiplied.xy, c.z);
+            }
+            case SPACE_HSV: {
+                c = vec3<f32>(c.x, unpremultiplied.yz);
+            }
+            default: {
+                c = unpremultiplied;
+            }
+        }
+    }
+
+    var rgb = vec3<f32>(0.0);
+    switch gradient.space {
+        case SPACE_SRGB: {
+            rgb = srgb_to_linear(c);
+        }
+        case SPACE_SRGB_LINEAR: {
+            rgb = c;
+        }
+        case SPACE_OKLAB: {
+            rgb = oklab_to_linear(c);
+        }
+        case SPACE_OKLCH: {
+            let h = radians(c.z);
+            rgb = oklab_to_linear(vec3<f32>(c.x, c.y * cos(h), c.y * sin(h)));
+        }
+        case SPACE_HSV: {
+            rgb = srgb_to_linear(hsv_to_srgb(c));
+        }
+        default: {}
+    }
+
+    return vec4<f32>(simulate_linear(rgb), alpha);
+}
+
+fn srgb_to_linear(rgb: vec3<f32>) -> vec3<f32> {
+    let c = abs(rgb);
+    let gamma = pow((c + 0.055) / 1.055, vec3<f32>(2.4));
+    let linear = select(gamma, c / 12.92, c <= vec3<f32>(0.04045));
+    return sign(rgb) * linear;
+}
+
+fn hsv_to_srgb(hsv: vec3<f32>) -> vec3<f32> {
+    let h = hsv.x - 360.0 * floor(hsv.x / 360.0);
+    let k = (vec3<f32>(5.0, 3.0, 1.0) + h / 60.0) % 6.0;
+    return hsv.z - hsv.z * hsv.y * clamp(min(k, 4.0 - k), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+fn oklab_to_linear(lab: vec3<f32>) -> vec3<f32> {
+    let l_ = lab.x + 0.3963377774 * lab.y + 0.2158037573 * lab.z;
+    let m_ = lab.x - 0.1055613458 * lab.y - 0.0638541728 * lab.z;
+    let s_ = lab.x - 0.0894841775 * lab.y - 1.2914855480 * lab.z;
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s = s_ * s_ * s_;
+
+    return vec3<f32>(
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    );
+}
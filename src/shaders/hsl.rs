@@ -0,0 +1,95 @@
+use cosmic::{
+    iced::{wgpu, Rectangle},
+    iced_wgpu::graphics::Viewport,
+    iced_widget::shader::{self, Storage},
+};
+
+use crate::{
+    cvd::Simulation,
+    shaders::{graph_shader, Cvd, ShaderPipeline},
+};
+
+// ---- Shader ----
+pub struct ColorGraph {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    /// Simulated color vision deficiency
+    pub cvd: Option<Simulation>,
+}
+
+impl<Message> shader::Program<Message> for ColorGraph {
+    type State = ();
+    type Primitive = Primitive;
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        cursor: cosmic::iced_core::mouse::Cursor,
+        bounds: cosmic::iced::Rectangle,
+    ) -> Self::Primitive {
+        Primitive::new(self.hue, self.saturation, self.lightness, self.cvd)
+    }
+}
+
+#[derive(Debug)]
+pub struct Primitive {
+    uniforms: Uniforms,
+    cvd: Cvd,
+}
+
+impl Primitive {
+    pub fn new(hue: f32, saturation: f32, lightness: f32, cvd: Option<Simulation>) -> Self {
+        Self {
+            uniforms: Uniforms {
+                hue,
+                saturation,
+                lightness,
+            },
+            cvd: Cvd::new(cvd),
+        }
+    }
+}
+
+impl shader::Primitive for Primitive {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        storage: &mut Storage,
+        bounds: &Rectangle,
+        viewport: &Viewport,
+    ) {
+        if !storage.has::<ShaderPipeline<Uniforms, 0>>() {
+            storage.store(ShaderPipeline::<Uniforms, 0>::new(
+                device,
+                queue,
+                format,
+                graph_shader!("hsl.wgsl"),
+            ));
+        }
+
+        let pipeline = storage.get_mut::<ShaderPipeline<Uniforms, 0>>().unwrap();
+        pipeline.write(queue, &self.uniforms, &self.cvd);
+    }
+
+    fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        storage: &Storage,
+        target: &wgpu::TextureView,
+        clip_bounds: &Rectangle<u32>,
+    ) {
+        let pipeline = storage.get::<ShaderPipeline<Uniforms, 0>>().unwrap();
+        pipeline.render(target, encoder, clip_bounds);
+    }
+}
+
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Uniforms {
+    hue: f32,
+    saturation: f32,
+    lightness: f32,
+}
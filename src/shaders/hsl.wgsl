@@ -0,0 +1,25 @@
+struct HSL {
+    hue: f32,
+    saturation: f32,
+    lightness: f32,
+}
+
+@group(0) @binding(0) var<uniform> hsl: HSL;
+
+@fragment
+fn fs_main(
+    @builtin(position) _clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+) -> @location(0) vec4<f32> {
+    // uv.x = saturation
+    // uv.y = lightness
+
+    // HSL to RGB
+    let a = uv.x * min(uv.y, 1.0 - uv.y);
+    let n = vec3<f32>(0.0, 8.0, 4.0);
+    let k = (n + hsl.hue / 30.0) % 12.0;
+    let rgb = uv.y - a * clamp(min(k - 3.0, 9.0 - k), vec3<f32>(-1.0), vec3<f32>(1.0));
+
+    let color = vec4<f32>(simulate_srgb(rgb), 1.0);
+    return color;
+}
@@ -1,45 +1,82 @@
 use cosmic::{
-    iced::{wgpu, Rectangle},
+    iced::{event, mouse, wgpu, Rectangle},
+    iced_core::Shell,
     iced_wgpu::graphics::Viewport,
     iced_widget::shader::{self, Storage},
 };
 
-use crate::shaders::ShaderPipeline;
+use crate::{
+    colorspace::ColorSpaceMessage,
+    cvd::Simulation,
+    shaders::{graph_shader, Cvd, Picking, ShaderPipeline},
+};
 
 // ---- Shader ----
 pub struct ColorGraph {
     pub hue: f32,
     pub saturation: f32,
     pub value: f32,
+    /// Simulated color vision deficiency
+    pub cvd: Option<Simulation>,
 }
 
-impl<Message> shader::Program<Message> for ColorGraph {
-    type State = ();
+impl shader::Program<ColorSpaceMessage> for ColorGraph {
+    type State = Picking;
     type Primitive = Primitive;
 
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: shader::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        _shell: &mut Shell<'_, ColorSpaceMessage>,
+    ) -> (event::Status, Option<ColorSpaceMessage>) {
+        let (status, uv) = state.update(&event, bounds, cursor);
+        (status, uv.map(pick))
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        state.mouse_interaction(bounds, cursor)
+    }
+
     fn draw(
         &self,
         state: &Self::State,
-        cursor: cosmic::iced_core::mouse::Cursor,
-        bounds: cosmic::iced::Rectangle,
+        cursor: mouse::Cursor,
+        bounds: Rectangle,
     ) -> Self::Primitive {
-        Primitive::new(self.hue, self.saturation, self.value)
+        Primitive::new(self.hue, self.saturation, self.value, self.cvd)
+    }
+}
+
+/// The saturation and value picked at `uv`
+pub fn pick([x, y]: [f32; 2]) -> ColorSpaceMessage {
+    ColorSpaceMessage::ChangeValues {
+        values: vec![(1, x), (2, y)],
     }
 }
 
 #[derive(Debug)]
 pub struct Primitive {
     uniforms: Uniforms,
+    cvd: Cvd,
 }
 
 impl Primitive {
-    pub fn new(hue: f32, saturation: f32, value: f32) -> Self {
+    pub fn new(hue: f32, saturation: f32, value: f32, cvd: Option<Simulation>) -> Self {
         Self {
             uniforms: Uniforms {
                 hue,
                 saturation,
                 value,
             },
+            cvd: Cvd::new(cvd),
         }
     }
 }
@@ -59,12 +96,12 @@ impl shader::Primitive for Primitive {
                 device,
                 queue,
                 format,
-                include_str!("hsv.wgsl"),
+                graph_shader!("crosshair.wgsl", "hsv.wgsl"),
             ));
         }
 
         let pipeline = storage.get_mut::<ShaderPipeline<Uniforms, 0>>().unwrap();
-        pipeline.write(queue, &self.uniforms);
+        pipeline.write(queue, &self.uniforms, &self.cvd);
     }
 
     fn render(
@@ -43,6 +43,6 @@ fn fs_main(
     }
 
     let m = uv.y - c;
-    let color = vec4<f32>(r1 + m, g1 + m, b1 + m, 1.0);
-    return color;
+    let color = vec4<f32>(simulate_srgb(vec3<f32>(r1 + m, g1 + m, b1 + m)), 1.0);
+    return crosshair(color, uv, vec2<f32>(hsv.saturation, hsv.value));
 }
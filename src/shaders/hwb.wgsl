@@ -0,0 +1,32 @@
+struct HWB {
+    hue: f32,
+    whiteness: f32,
+    blackness: f32,
+}
+
+@group(0) @binding(0) var<uniform> hwb: HWB;
+
+@fragment
+fn fs_main(
+    @builtin(position) _clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+) -> @location(0) vec4<f32> {
+    // uv.x = whiteness
+    // uv.y = blackness
+
+    // Gray once whiteness and blackness add up to 1
+    let sum = uv.x + uv.y;
+    if sum >= 1.0 {
+        let gray = uv.x / sum;
+        return vec4<f32>(gray, gray, gray, 1.0);
+    }
+
+    // HWB to RGB, the pure hue is HSL with full saturation and half lightness
+    let n = vec3<f32>(0.0, 8.0, 4.0);
+    let k = (n + hwb.hue / 30.0) % 12.0;
+    let hue = 0.5 - 0.5 * clamp(min(k - 3.0, 9.0 - k), vec3<f32>(-1.0), vec3<f32>(1.0));
+    let rgb = hue * (1.0 - uv.x - uv.y) + uv.x;
+
+    let color = vec4<f32>(simulate_srgb(rgb), 1.0);
+    return color;
+}
@@ -0,0 +1,86 @@
+struct LAB {
+    lightness: f32,
+    green_red: f32,
+    blue_yellow: f32,
+    mode: u32,
+    d65: u32,
+}
+
+const MODE_LIGHTNESS = 0u;
+const MODE_GREEN_RED = 1u;
+const MODE_BLUE_YELLOW = 2u;
+
+@group(0) @binding(0) var<uniform> lab: LAB;
+
+@fragment
+fn fs_main(
+    @builtin(position) _clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+) -> @location(0) vec4<f32> {
+    let d65 = lab.d65 != 0u;
+
+    var rgb = vec3<f32>(0.0);
+    switch lab.mode {
+        case MODE_LIGHTNESS: {
+            let lightness = uv.x * 100.0;
+
+            rgb = lab_to_rgb(lightness, lab.green_red, lab.blue_yellow, d65);
+        }
+        case MODE_GREEN_RED: {
+            let lightness = uv.y * 100.0;
+            let green_red = (uv.x - 0.5) * 250.0;
+
+            rgb = lab_to_rgb(lightness, green_red, lab.blue_yellow, d65);
+        }
+        case MODE_BLUE_YELLOW: {
+            let lightness = uv.y * 100.0;
+            let blue_yellow = (uv.x - 0.5) * 250.0;
+
+            rgb = lab_to_rgb(lightness, lab.green_red, blue_yellow, d65);
+        }
+        default: {}
+    }
+
+    var color = vec4<f32>(rgb, 1.0);
+    if max(color.x, max(color.y, color.z)) > 1.0 || min(color.x, min(color.y, color.z)) < 0.0 {
+        color.w = 0.1;
+    }
+    color = vec4<f32>(simulate_linear(color.xyz), color.w);
+    return color;
+}
+
+const KAPPA = 24389.0 / 27.0;
+const EPSILON = 216.0 / 24389.0;
+
+const D50 = vec3<f32>(0.9642957, 1.0, 1.1040446);
+const D65 = vec3<f32>(0.9504559, 1.0, 1.0890578);
+
+// Returns linear sRGB
+fn lab_to_rgb(l: f32, a: f32, b: f32, d65: bool) -> vec3<f32> {
+    let f1 = (l + 16.0) / 116.0;
+    let f0 = a / 500.0 + f1;
+    let f2 = f1 - b / 200.0;
+
+    let x = select((116.0 * f0 - 16.0) / KAPPA, f0 * f0 * f0, f0 * f0 * f0 > EPSILON);
+    let y = select(l / KAPPA, f1 * f1 * f1, l > KAPPA * EPSILON);
+    let z = select((116.0 * f2 - 16.0) / KAPPA, f2 * f2 * f2, f2 * f2 * f2 > EPSILON);
+
+    var xyz = vec3<f32>(x, y, z);
+    if d65 {
+        xyz *= D65;
+    } else {
+        // Bradford adaptation from D50 to D65
+        xyz *= D50;
+        xyz = vec3<f32>(
+            dot(vec3<f32>(0.9554734, -0.0230985, 0.0632592), xyz),
+            dot(vec3<f32>(-0.0283697, 1.0099954, 0.0210414), xyz),
+            dot(vec3<f32>(0.0123140, -0.0205076, 1.3303659), xyz),
+        );
+    }
+
+    return vec3<f32>(
+        dot(vec3<f32>(3.2409699, -1.5373832, -0.4986108), xyz),
+        dot(vec3<f32>(-0.9692436, 1.8759675, 0.0415551), xyz),
+        dot(vec3<f32>(0.0556301, -0.2039770, 1.0569715), xyz),
+    );
+}
@@ -0,0 +1,101 @@
+use cosmic::{
+    iced::{wgpu, Rectangle},
+    iced_wgpu::graphics::Viewport,
+    iced_widget::shader::{self, Storage},
+};
+
+use crate::{
+    cvd::Simulation,
+    shaders::{graph_shader, Cvd, ShaderPipeline},
+};
+
+// ---- Shader ----
+pub struct ColorGraph<const MODE: u32> {
+    pub lightness: f32,
+    pub chroma: f32,
+    pub hue: f32,
+    /// Relative to D65 instead of D50
+    pub d65: bool,
+    /// Simulated color vision deficiency
+    pub cvd: Option<Simulation>,
+}
+
+impl<const M: u32, Message> shader::Program<Message> for ColorGraph<M> {
+    type State = ();
+    type Primitive = Primitive<M>;
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        cursor: cosmic::iced_core::mouse::Cursor,
+        bounds: cosmic::iced::Rectangle,
+    ) -> Self::Primitive {
+        Primitive::<M>::new(self.lightness, self.chroma, self.hue, self.d65, self.cvd)
+    }
+}
+
+#[derive(Debug)]
+pub struct Primitive<const M: u32> {
+    uniforms: Uniforms,
+    cvd: Cvd,
+}
+
+impl<const M: u32> Primitive<M> {
+    pub fn new(lightness: f32, chroma: f32, hue: f32, d65: bool, cvd: Option<Simulation>) -> Self {
+        Self {
+            uniforms: Uniforms {
+                lightness,
+                chroma,
+                hue,
+                mode: M,
+                d65: d65 as u32,
+            },
+            cvd: Cvd::new(cvd),
+        }
+    }
+}
+
+impl<const M: u32> shader::Primitive for Primitive<M> {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        storage: &mut Storage,
+        bounds: &Rectangle,
+        viewport: &Viewport,
+    ) {
+        if !storage.has::<ShaderPipeline<Uniforms, M>>() {
+            storage.store(ShaderPipeline::<Uniforms, M>::new(
+                device,
+                queue,
+                format,
+                graph_shader!("lch.wgsl"),
+            ));
+        }
+
+        let pipeline = storage.get_mut::<ShaderPipeline<Uniforms, M>>().unwrap();
+        pipeline.write(queue, &self.uniforms, &self.cvd);
+    }
+
+    fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        storage: &Storage,
+        target: &wgpu::TextureView,
+        clip_bounds: &Rectangle<u32>,
+    ) {
+        let pipeline = storage.get::<ShaderPipeline<Uniforms, M>>().unwrap();
+        pipeline.render(target, encoder, clip_bounds);
+    }
+}
+
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Uniforms {
+    lightness: f32,
+    chroma: f32,
+    hue: f32,
+    mode: u32,
+    d65: u32,
+}
@@ -0,0 +1,92 @@
+struct LCH {
+    lightness: f32,
+    chroma: f32,
+    hue: f32,
+    mode: u32,
+    d65: u32,
+}
+
+const MODE_LIGHTNESS = 0u;
+const MODE_CHROMA = 1u;
+const MODE_HUE = 2u;
+
+@group(0) @binding(0) var<uniform> lch: LCH;
+
+@fragment
+fn fs_main(
+    @builtin(position) _clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+) -> @location(0) vec4<f32> {
+    let d65 = lch.d65 != 0u;
+
+    var rgb = vec3<f32>(0.0);
+    switch lch.mode {
+        case MODE_LIGHTNESS: {
+            let lightness = uv.x * 100.0;
+            let chroma = uv.y * 150.0;
+
+            rgb = lch_to_rgb(lightness, chroma, lch.hue, d65);
+        }
+        case MODE_CHROMA: {
+            let chroma = uv.x * 150.0;
+            let hue = uv.y * 360.0;
+
+            rgb = lch_to_rgb(lch.lightness, chroma, hue, d65);
+        }
+        case MODE_HUE: {
+            let lightness = uv.y * 100.0;
+            let hue = uv.x * 360.0;
+
+            rgb = lch_to_rgb(lightness, lch.chroma, hue, d65);
+        }
+        default: {}
+    }
+
+    var color = vec4<f32>(rgb, 1.0);
+    if max(color.x, max(color.y, color.z)) > 1.0 || min(color.x, min(color.y, color.z)) < 0.0 {
+        color.w = 0.1;
+    }
+    color = vec4<f32>(simulate_linear(color.xyz), color.w);
+    return color;
+}
+
+fn lch_to_rgb(l: f32, c: f32, h: f32, d65: bool) -> vec3<f32> {
+    let hue = radians(h);
+    return lab_to_rgb(l, c * cos(hue), c * sin(hue), d65);
+}
+
+const KAPPA = 24389.0 / 27.0;
+const EPSILON = 216.0 / 24389.0;
+
+const D50 = vec3<f32>(0.9642957, 1.0, 1.1040446);
+const D65 = vec3<f32>(0.9504559, 1.0, 1.0890578);
+
+// Returns linear sRGB
+fn lab_to_rgb(l: f32, a: f32, b: f32, d65: bool) -> vec3<f32> {
+    let f1 = (l + 16.0) / 116.0;
+    let f0 = a / 500.0 + f1;
+    let f2 = f1 - b / 200.0;
+
+    let x = select((116.0 * f0 - 16.0) / KAPPA, f0 * f0 * f0, f0 * f0 * f0 > EPSILON);
+    let y = select(l / KAPPA, f1 * f1 * f1, l > KAPPA * EPSILON);
+    let z = select((116.0 * f2 - 16.0) / KAPPA, f2 * f2 * f2, f2 * f2 * f2 > EPSILON);
+
+    var xyz = vec3<f32>(x, y, z);
+    if d65 {
+        xyz *= D65;
+    } else {
+        // Bradford adaptation from D50 to D65
+        xyz *= D50;
+        xyz = vec3<f32>(
+            dot(vec3<f32>(0.9554734, -0.0230985, 0.0632592), xyz),
+            dot(vec3<f32>(-0.0283697, 1.0099954, 0.0210414), xyz),
+            dot(vec3<f32>(0.0123140, -0.0205076, 1.3303659), xyz),
+        );
+    }
+
+    return vec3<f32>(
+        dot(vec3<f32>(3.2409699, -1.5373832, -0.4986108), xyz),
+        dot(vec3<f32>(-0.9692436, 1.8759675, 0.0415551), xyz),
+        dot(vec3<f32>(0.0556301, -0.2039770, 1.0569715), xyz),
+    );
+}
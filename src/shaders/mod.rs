@@ -1,15 +1,152 @@
+pub mod chromaticity;
+pub mod disc;
+pub mod gradient;
+pub mod hsl;
 pub mod hsv;
+pub mod hwb;
+pub mod lab;
+pub mod lch;
 pub mod oklab;
 pub mod oklch;
+pub mod raster;
+pub mod wheel;
 
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-use cosmic::iced::{wgpu, Rectangle};
+use cosmic::{
+    iced::{event, mouse, wgpu, Point, Rectangle},
+    iced_widget::shader,
+};
+
+use crate::cvd::Simulation;
+
+/// Prefixes graph shaders with the color vision deficiency simulation
+macro_rules! graph_shader {
+    ($($file:literal),+) => {
+        concat!(include_str!("cvd.wgsl"), $(include_str!($file)),+)
+    };
+}
+pub(crate) use graph_shader;
+
+/// Whether the window draws with wgpu, as seen by
+/// [`RendererProbe`](crate::widgets::RendererProbe)
+static GPU_AVAILABLE: AtomicBool = AtomicBool::new(true);
+
+/// Whether the shader widget can draw, assumed until the window has drawn
+/// with another renderer. Without it the graphs fall back to [`raster`].
+pub fn gpu_available() -> bool {
+    GPU_AVAILABLE.load(Ordering::Relaxed)
+}
+
+/// Records the renderer the window draws with, returning whether it changed
+pub fn set_gpu_available(available: bool) -> bool {
+    let changed = GPU_AVAILABLE.swap(available, Ordering::Relaxed) != available;
+    if changed && !available {
+        log::warn!("Drawing the graphs on the CPU without wgpu");
+    }
+    changed
+}
+
+/// The state of a graph picking its components with the mouse
+#[derive(Debug, Default)]
+pub struct Picking {
+    dragging: bool,
+}
+
+impl Picking {
+    /// The position a press or drag picks, from 0 to 1 like the uv of the
+    /// shaders, so y goes up
+    pub fn update(
+        &mut self,
+        event: &shader::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<[f32; 2]>) {
+        match event {
+            shader::Event::Mouse(event) => self.update_mouse(event, bounds, cursor),
+            _ => (event::Status::Ignored, None),
+        }
+    }
+
+    /// [`Picking::update`] for the mouse events of any widget
+    pub fn update_mouse(
+        &mut self,
+        event: &mouse::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<[f32; 2]>) {
+        let position = match event {
+            mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                let Some(position) = cursor.position_in(bounds) else {
+                    return (event::Status::Ignored, None);
+                };
+                self.dragging = true;
+                position
+            }
+            // Keeps picking outside of the graph until the button is released
+            mouse::Event::CursorMoved { position } if self.dragging => {
+                Point::new(position.x - bounds.x, position.y - bounds.y)
+            }
+            mouse::Event::ButtonReleased(mouse::Button::Left) if self.dragging => {
+                self.dragging = false;
+                return (event::Status::Captured, None);
+            }
+            _ => return (event::Status::Ignored, None),
+        };
+
+        let uv = [
+            (position.x / bounds.width).clamp(0.0, 1.0),
+            (1.0 - position.y / bounds.height).clamp(0.0, 1.0),
+        ];
+        (event::Status::Captured, Some(uv))
+    }
+
+    pub fn mouse_interaction(
+        &self,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if self.dragging || cursor.is_over(bounds) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+/// The color vision deficiency matrix of a graph, with the WGSL `mat3x3`
+/// layout of padded columns
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct Cvd {
+    columns: [[f32; 4]; 3],
+}
+
+impl Cvd {
+    pub fn new(simulation: Option<Simulation>) -> Self {
+        let columns = match simulation {
+            Some(simulation) => {
+                let m = simulation.matrix();
+                [0, 1, 2].map(|column| [m[0][column], m[1][column], m[2][column], 0.0])
+            }
+            None => [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+            ],
+        };
+        Self { columns }
+    }
+}
 
 pub struct ShaderPipeline<T, const ID: u32> {
     pipeline: wgpu::RenderPipeline,
     bind_group: wgpu::BindGroup,
     data: wgpu::Buffer,
+    cvd: wgpu::Buffer,
     phantom: PhantomData<T>,
 }
 
@@ -27,28 +164,53 @@ impl<T: bytemuck::Pod, const ID: u32> ShaderPipeline<T, ID> {
             mapped_at_creation: false,
         });
 
+        let cvd_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shader cvd buffer"),
+            size: std::mem::size_of::<Cvd>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         let uniform_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("shader uniform bind group layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
                     },
-                    count: None,
-                }],
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
             });
 
         let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("shader uniform bind group"),
             layout: &uniform_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: data_buffer.as_entire_binding(),
-            }],
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: data_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: cvd_buffer.as_entire_binding(),
+                },
+            ],
         });
 
         let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -99,12 +261,14 @@ impl<T: bytemuck::Pod, const ID: u32> ShaderPipeline<T, ID> {
             pipeline,
             bind_group: uniform_bind_group,
             data: data_buffer,
+            cvd: cvd_buffer,
             phantom: PhantomData::default(),
         }
     }
 
-    pub fn write(&self, queue: &wgpu::Queue, data: &T) {
+    pub fn write(&self, queue: &wgpu::Queue, data: &T, cvd: &Cvd) {
         queue.write_buffer(&self.data, 0, bytemuck::bytes_of(data));
+        queue.write_buffer(&self.cvd, 0, bytemuck::bytes_of(cvd));
     }
 
     pub fn render(
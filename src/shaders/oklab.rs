@@ -1,39 +1,80 @@
 use cosmic::{
-    iced::{wgpu, Rectangle},
+    iced::{event, mouse, wgpu, Rectangle},
+    iced_core::Shell,
     iced_wgpu::{self, graphics::Viewport},
     iced_widget::shader::{self, Storage},
 };
 
-use crate::shaders::ShaderPipeline;
+use crate::{
+    colorspace::ColorSpaceMessage,
+    cvd::Simulation,
+    shaders::{graph_shader, Cvd, Picking, ShaderPipeline},
+};
 
 // ---- Shader ----
 pub struct ColorGraph<const MODE: u32> {
     pub lightness: f32,
     pub green_red: f32,
     pub blue_yellow: f32,
+    /// Simulated color vision deficiency
+    pub cvd: Option<Simulation>,
 }
 
-impl<const M: u32, Message> shader::Program<Message> for ColorGraph<M> {
-    type State = ();
+impl<const M: u32> shader::Program<ColorSpaceMessage> for ColorGraph<M> {
+    type State = Picking;
     type Primitive = Primitive<M>;
 
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: shader::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        _shell: &mut Shell<'_, ColorSpaceMessage>,
+    ) -> (event::Status, Option<ColorSpaceMessage>) {
+        let (status, uv) = state.update(&event, bounds, cursor);
+        (status, uv.map(|uv| pick(M, uv)))
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        state.mouse_interaction(bounds, cursor)
+    }
+
     fn draw(
         &self,
         state: &Self::State,
-        cursor: cosmic::iced_core::mouse::Cursor,
-        bounds: cosmic::iced::Rectangle,
+        cursor: mouse::Cursor,
+        bounds: Rectangle,
     ) -> Self::Primitive {
-        Primitive::<M>::new(self.lightness, self.green_red, self.blue_yellow)
+        Primitive::<M>::new(self.lightness, self.green_red, self.blue_yellow, self.cvd)
+    }
+}
+
+/// The components picked at `uv` on the graph of `mode`
+pub fn pick(mode: u32, [x, y]: [f32; 2]) -> ColorSpaceMessage {
+    ColorSpaceMessage::ChangeValues {
+        values: match mode {
+            // The lightness graph is a strip
+            0 => vec![(0, x)],
+            1 => vec![(1, x - 0.5), (0, y)],
+            _ => vec![(2, x - 0.5), (0, y)],
+        },
     }
 }
 
 #[derive(Debug)]
 pub struct Primitive<const M: u32> {
     uniforms: Uniforms,
+    cvd: Cvd,
 }
 
 impl<const M: u32> Primitive<M> {
-    pub fn new(lightness: f32, green_red: f32, blue_yellow: f32) -> Self {
+    pub fn new(lightness: f32, green_red: f32, blue_yellow: f32, cvd: Option<Simulation>) -> Self {
         Self {
             uniforms: Uniforms {
                 lightness,
@@ -41,6 +82,7 @@ impl<const M: u32> Primitive<M> {
                 blue_yellow,
                 mode: M,
             },
+            cvd: Cvd::new(cvd),
         }
     }
 }
@@ -61,12 +103,12 @@ impl<const M: u32> shader::Primitive for Primitive<M> {
                 device,
                 queue,
                 format,
-                include_str!("oklab.wgsl"),
+                graph_shader!("crosshair.wgsl", "oklab.wgsl"),
             ));
         }
 
         let pipeline = storage.get_mut::<ShaderPipeline<Uniforms, M>>().unwrap();
-        pipeline.write(queue, &self.uniforms);
+        pipeline.write(queue, &self.uniforms, &self.cvd);
     }
 
     fn render(
@@ -17,23 +17,27 @@ fn fs_main(
     @location(0) uv: vec2<f32>,
 ) -> @location(0) vec4<f32> {
     var rgb = vec3<f32>(0.0);
+    var marker = vec2<f32>(0.0);
     switch oklab.mode {
         case MODE_LIGHTNESS: {
             let lightness = uv.x;
 
             rgb = oklab_to_rgb(lightness, oklab.green_red, oklab.blue_yellow);
+            marker = vec2<f32>(oklab.lightness, 0.5);
         }
         case MODE_GREEN_RED: {
             let lightness = uv.y;
             let green_red = uv.x - 0.5;
 
             rgb = oklab_to_rgb(lightness, green_red, oklab.blue_yellow);
+            marker = vec2<f32>(oklab.green_red + 0.5, oklab.lightness);
         }
         case MODE_BLUE_YELLOW: {
             let lightness = uv.y;
             let blue_yellow = uv.x - 0.5;
 
             rgb = oklab_to_rgb(lightness, oklab.green_red, blue_yellow);
+            marker = vec2<f32>(oklab.blue_yellow + 0.5, oklab.lightness);
         }
         default: {}
     }
@@ -42,7 +46,8 @@ fn fs_main(
     if max(color.x, max(color.y, color.z)) > 1.0 || min(color.x, min(color.y, color.z)) < 0.0 {
         color.w = 0.1;
     }
-    return color;
+    color = vec4<f32>(simulate_linear(color.xyz), color.w);
+    return crosshair(color, uv, marker);
 }
 
 fn oklab_to_rgb(okl: f32, a: f32, b: f32) -> vec3<f32> {
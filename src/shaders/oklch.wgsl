@@ -17,24 +17,28 @@ fn fs_main(
     @location(0) uv: vec2<f32>,
 ) -> @location(0) vec4<f32> {
     var rgb = vec3<f32>(0.0);
+    var marker = vec2<f32>(0.0);
     switch oklch.mode {
         case MODE_LIGHTNESS: {
             let chroma = uv.y * 0.37;
             let lightness = uv.x;
             
             rgb = oklch_to_rgb(lightness, chroma, oklch.hue);
+            marker = vec2<f32>(oklch.lightness, oklch.chroma / 0.37);
         }
         case MODE_CHROMA: {
             let chroma = uv.x * 0.37;
             let hue = uv.y * 360.0;
 
             rgb = oklch_to_rgb(oklch.lightness, chroma, hue);
+            marker = vec2<f32>(oklch.chroma / 0.37, oklch.hue / 360.0);
         }
         case MODE_HUE: {
             let lightness = uv.y;
             let hue = uv.x * 360.0;
 
             rgb = oklch_to_rgb(lightness, oklch.chroma, hue);
+            marker = vec2<f32>(oklch.hue / 360.0, oklch.lightness);
         }
         default: {}
     }
@@ -43,7 +47,8 @@ fn fs_main(
     if max(color.x, max(color.y, color.z)) > 1.0 || min(color.x, min(color.y, color.z)) < 0.0 {
         color.w = 0.1;
     }
-    return color;
+    color = vec4<f32>(simulate_linear(color.xyz), color.w);
+    return crosshair(color, uv, marker);
 }
 
 fn oklch_to_rgb(okl: f32, okc: f32, okh: f32) -> vec3<f32> {
@@ -0,0 +1,350 @@
+//! CPU rasterization of the HSV, OKLab and OKLCh graphs into images, for
+//! when wgpu is unavailable and the shader widget draws nothing. Every pixel
+//! is evaluated like the fragment shaders, crosshair included.
+
+use std::sync::Mutex;
+
+use cosmic::{
+    iced::{ContentFit, Length},
+    widget::{self, image},
+};
+use once_cell::sync::Lazy;
+
+use crate::{
+    colorspace::{cie, hsv_to_rgb, oklab_to_linear_rgb, ColorSpaceMessage},
+    cvd::Simulation,
+    shaders::{self, oklch::MAX_CHROMA},
+    widgets::pick_area,
+};
+
+/// The pixels of a component graph, stretched over the width of its card
+const COMPONENT_SIZE: [u32; 2] = [256, 64];
+/// The alpha of colors outside of sRGB, like the shaders
+const OUT_OF_GAMUT_ALPHA: f32 = 0.1;
+/// The most images kept by [`CACHE`], enough for every graph of a few columns
+const CACHE_SIZE: usize = 32;
+
+/// Recently rasterized graphs, least recently used first. Reusing their
+/// handles keeps them from being rasterized and uploaded on every view.
+static CACHE: Lazy<Mutex<Vec<(Plane, Option<Simulation>, [u32; 2], image::Handle)>>> =
+    Lazy::new(|| Mutex::new(Vec::with_capacity(CACHE_SIZE)));
+
+/// A graph as the uniforms of its shader, with `mode` being the component
+/// of the component graphs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Plane {
+    /// Saturation to the right and value up, see `hsv.wgsl`
+    Hsv {
+        hue: f32,
+        saturation: f32,
+        value: f32,
+    },
+    /// See `oklab.wgsl`
+    Oklab {
+        lightness: f32,
+        green_red: f32,
+        blue_yellow: f32,
+        mode: u32,
+    },
+    /// See `oklch.wgsl`
+    Oklch {
+        lightness: f32,
+        chroma: f32,
+        hue: f32,
+        mode: u32,
+    },
+}
+
+impl Plane {
+    /// The linear sRGB at `uv`, which goes from 0 to 1 with y up
+    fn linear(&self, [x, y]: [f32; 2]) -> [f32; 3] {
+        match *self {
+            Plane::Hsv { hue, .. } => hsv_to_rgb(hue, x, y).map(cie::srgb_to_linear),
+            Plane::Oklab {
+                green_red,
+                blue_yellow,
+                mode,
+                ..
+            } => match mode {
+                0 => oklab_to_linear_rgb(x, green_red, blue_yellow),
+                1 => oklab_to_linear_rgb(y, x - 0.5, blue_yellow),
+                _ => oklab_to_linear_rgb(y, green_red, x - 0.5),
+            },
+            Plane::Oklch {
+                lightness,
+                chroma,
+                hue,
+                mode,
+            } => {
+                let [l, c, h] = match mode {
+                    0 => [x, y * MAX_CHROMA, hue],
+                    1 => [lightness, x * MAX_CHROMA, y * 360.0],
+                    _ => [y, chroma, x * 360.0],
+                };
+                let (sin, cos) = h.to_radians().sin_cos();
+                oklab_to_linear_rgb(l, c * cos, c * sin)
+            }
+        }
+    }
+
+    /// The uv of the crosshair
+    fn marker(&self) -> [f32; 2] {
+        match *self {
+            Plane::Hsv {
+                saturation, value, ..
+            } => [saturation, value],
+            Plane::Oklab {
+                lightness,
+                green_red,
+                blue_yellow,
+                mode,
+            } => match mode {
+                0 => [lightness, 0.5],
+                1 => [green_red + 0.5, lightness],
+                _ => [blue_yellow + 0.5, lightness],
+            },
+            Plane::Oklch {
+                lightness,
+                chroma,
+                hue,
+                mode,
+            } => match mode {
+                0 => [lightness, chroma / MAX_CHROMA],
+                1 => [chroma / MAX_CHROMA, hue / 360.0],
+                _ => [hue / 360.0, lightness],
+            },
+        }
+    }
+
+    /// The components picked at `uv`, like the shader of the graph
+    fn pick(&self, uv: [f32; 2]) -> ColorSpaceMessage {
+        match *self {
+            Plane::Hsv { .. } => shaders::hsv::pick(uv),
+            Plane::Oklab { mode, .. } => shaders::oklab::pick(mode, uv),
+            Plane::Oklch { mode, .. } => shaders::oklch::pick(mode, uv),
+        }
+    }
+
+    /// The sRGB and alpha at `uv` as seen with `cvd`, fading colors outside
+    /// of sRGB like the shaders (HSV never leaves it)
+    pub fn color(&self, uv: [f32; 2], cvd: Option<Simulation>) -> [f32; 4] {
+        let linear = self.linear(uv);
+        let alpha = if linear.iter().all(|c| (0.0..=1.0).contains(c)) {
+            1.0
+        } else {
+            OUT_OF_GAMUT_ALPHA
+        };
+
+        let linear = match cvd {
+            Some(simulation) => cie::mul(&simulation.matrix(), linear),
+            None => linear,
+        };
+        let [r, g, b] = linear.map(cie::linear_to_srgb);
+        [r, g, b, alpha]
+    }
+}
+
+/// Evaluates `plane` at the center of every pixel into RGBA8, top row first
+pub fn rasterize(plane: Plane, cvd: Option<Simulation>, width: u32, height: u32) -> Vec<u8> {
+    let size = [width as f32, height as f32];
+    let marker = plane.marker();
+    let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+
+    for row in 0..height {
+        for column in 0..width {
+            let uv = [
+                (column as f32 + 0.5) / size[0],
+                1.0 - (row as f32 + 0.5) / size[1],
+            ];
+
+            let color = crosshair(uv, marker, size).unwrap_or_else(|| plane.color(uv, cvd));
+            // `max` first, turning NaN into black
+            pixels.extend(color.map(|c| (c.max(0.0).min(1.0) * 255.0).round() as u8));
+        }
+    }
+
+    pixels
+}
+
+/// The color of the crosshair of `crosshair.wgsl` at `uv`, if it covers it
+fn crosshair(uv: [f32; 2], marker: [f32; 2], size: [f32; 2]) -> Option<[f32; 4]> {
+    // Distances in pixels
+    let distance = [0, 1].map(|i| (uv[i] - marker[i]).abs() * size[i]);
+    let arm = distance[0].max(distance[1]);
+    let line = distance[0].min(distance[1]);
+
+    if arm > 3.0 && arm < 10.0 {
+        if line < 1.0 {
+            return Some([1.0; 4]);
+        } else if line < 2.0 {
+            return Some([0.0, 0.0, 0.0, 1.0]);
+        }
+    }
+
+    None
+}
+
+/// The image of `plane` at `width` by `height` pixels, from [`CACHE`] if
+/// it was rasterized recently
+fn cached_image(plane: Plane, cvd: Option<Simulation>, width: u32, height: u32) -> image::Handle {
+    let mut cache = CACHE.lock().unwrap();
+    let size = [width, height];
+    let cached = cache
+        .iter()
+        .position(|entry| entry.0 == plane && entry.1 == cvd && entry.2 == size);
+
+    let entry = match cached {
+        Some(index) => cache.remove(index),
+        None => {
+            if cache.len() == CACHE_SIZE {
+                cache.remove(0);
+            }
+            let pixels = rasterize(plane, cvd, width, height);
+            (
+                plane,
+                cvd,
+                size,
+                image::Handle::from_rgba(width, height, pixels),
+            )
+        }
+    };
+
+    let handle = entry.3.clone();
+    cache.push(entry);
+    handle
+}
+
+/// `plane` rasterized at `width` by `height` pixels, shown at that size
+pub fn graph<'a>(
+    plane: Plane,
+    cvd: Option<Simulation>,
+    width: u32,
+    height: u32,
+) -> cosmic::Element<'a, ColorSpaceMessage> {
+    let image = widget::image(cached_image(plane, cvd, width, height))
+        .width(width as f32)
+        .height(height as f32);
+    pick_area(image, move |uv| plane.pick(uv)).into()
+}
+
+/// `plane` rasterized as a component graph, filling the width of its card
+pub fn component_graph<'a>(
+    plane: Plane,
+    cvd: Option<Simulation>,
+) -> cosmic::Element<'a, ColorSpaceMessage> {
+    let [width, height] = COMPONENT_SIZE;
+    let image = widget::image(cached_image(plane, cvd, width, height))
+        .width(Length::Fill)
+        .height(height as f32)
+        .content_fit(ContentFit::Fill);
+    pick_area(image, move |uv| plane.pick(uv)).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rasterize, Plane};
+    use crate::cvd::{Deficiency, Simulation};
+
+    /// The RGBA8 of the pixel at `column` and `row`
+    fn pixel(pixels: &[u8], width: u32, column: u32, row: u32) -> [u8; 4] {
+        let start = ((row * width + column) * 4) as usize;
+        pixels[start..start + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn hsv() {
+        let plane = Plane::Hsv {
+            hue: 120.0,
+            saturation: 0.0,
+            value: 0.0,
+        };
+        let pixels = rasterize(plane, None, 20, 20);
+        assert_eq!(pixels.len(), 20 * 20 * 4);
+
+        // Saturation across and value up, from the pixel centers
+        assert_eq!(pixel(&pixels, 20, 19, 0), [6, 249, 6, 255]);
+        assert_eq!(pixel(&pixels, 20, 5, 10), [88, 121, 88, 255]);
+        assert_eq!(pixel(&pixels, 20, 10, 19), [3, 6, 3, 255]);
+    }
+
+    #[test]
+    fn crosshair() {
+        let plane = Plane::Hsv {
+            hue: 0.0,
+            saturation: 0.5,
+            value: 0.5,
+        };
+        let pixels = rasterize(plane, None, 20, 20);
+
+        // White arms with dark outlines, leaving the picked color visible
+        assert_eq!(pixel(&pixels, 20, 15, 10), [255; 4]);
+        assert_eq!(pixel(&pixels, 20, 15, 11), [0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, 20, 10, 5), [255; 4]);
+        assert_eq!(pixel(&pixels, 20, 10, 10), [121, 58, 58, 255]);
+        assert_eq!(pixel(&pixels, 20, 15, 15), [57, 13, 13, 255]);
+    }
+
+    #[test]
+    fn oklab_lightness() {
+        let plane = Plane::Oklab {
+            lightness: 0.0,
+            green_red: 0.0,
+            blue_yellow: 0.0,
+            mode: 0,
+        };
+        let pixels = rasterize(plane, None, 40, 4);
+
+        // A gray ramp, the sRGB of lightness cubed
+        assert_eq!(pixel(&pixels, 40, 18, 0), [89, 89, 89, 255]);
+        assert_eq!(pixel(&pixels, 40, 39, 3), [251, 251, 251, 255]);
+    }
+
+    #[test]
+    fn oklch_out_of_gamut() {
+        let plane = Plane::Oklch {
+            lightness: 0.6,
+            chroma: 0.0,
+            hue: 0.0,
+            mode: 1,
+        };
+        let pixels = rasterize(plane, None, 40, 40);
+
+        // Chroma grows to the right, fading where it leaves sRGB
+        assert_eq!(pixel(&pixels, 40, 25, 0)[3], 255);
+        assert_eq!(pixel(&pixels, 40, 28, 0)[3], 26);
+        assert_eq!(pixel(&pixels, 40, 39, 0)[3], 26);
+        assert_eq!(pixel(&pixels, 40, 39, 20)[3], 26);
+    }
+
+    #[test]
+    fn simulation() {
+        let plane = Plane::Hsv {
+            hue: 0.0,
+            saturation: 0.0,
+            value: 0.0,
+        };
+        let simulation = Simulation {
+            deficiency: Deficiency::Protan,
+            severity: 1.0,
+        };
+        let normal = rasterize(plane, None, 20, 20);
+        let simulated = rasterize(plane, Some(simulation), 20, 20);
+
+        // Like the swatches, leaving the crosshair white
+        let [r, g, b, _] = pixel(&normal, 20, 19, 0);
+        let expected = simulation
+            .apply([r, g, b].map(|c| c as f32 / 255.0))
+            .map(|c| (c * 255.0).round() as u8);
+        let [r, g, b, _] = pixel(&simulated, 20, 19, 0);
+        assert!(
+            [r, g, b]
+                .iter()
+                .zip(expected)
+                .all(|(a, b)| a.abs_diff(b) <= 1),
+            "{:?} {:?}",
+            [r, g, b],
+            expected
+        );
+        assert_eq!(pixel(&simulated, 20, 0, 10), [255; 4]);
+    }
+}
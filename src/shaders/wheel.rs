@@ -0,0 +1,218 @@
+use cosmic::{
+    iced::{event, mouse, wgpu, Point, Rectangle},
+    iced_core::Shell,
+    iced_wgpu::graphics::Viewport,
+    iced_widget::shader::{self, Storage},
+};
+
+use crate::{
+    cvd::Simulation,
+    shaders::{graph_shader, Cvd, ShaderPipeline},
+    wheel::{self, WheelShape, RING_INNER},
+};
+
+// ---- Shader ----
+/// A hue ring around a saturation and value triangle or square
+pub struct ColorWheel<'a, Message> {
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+    pub shape: WheelShape,
+    /// Simulated color vision deficiency
+    pub cvd: Option<Simulation>,
+    /// Called with the picked hue, saturation and value
+    pub on_change: Box<dyn Fn([f32; 3]) -> Message + 'a>,
+}
+
+impl<'a, Message> ColorWheel<'a, Message> {
+    /// The hue of gray colors is lost, so the last picked hue is kept for them
+    fn hue(&self, state: &State) -> f32 {
+        match state.hue {
+            Some(hue) if self.saturation <= 0.0 || self.value <= 0.0 => hue,
+            _ => self.hue,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Ring,
+    Shape,
+}
+
+#[derive(Debug, Default)]
+pub struct State {
+    /// The part being dragged
+    target: Option<Target>,
+    hue: Option<f32>,
+}
+
+impl<'a, Message> shader::Program<Message> for ColorWheel<'a, Message> {
+    type State = State;
+    type Primitive = Primitive;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: shader::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        _shell: &mut Shell<'_, Message>,
+    ) -> (event::Status, Option<Message>) {
+        let position = match event {
+            shader::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let Some(position) = cursor.position_in(bounds) else {
+                    return (event::Status::Ignored, None);
+                };
+
+                let position = picker_position(position, bounds);
+                state.target = if wheel::in_ring(position) {
+                    Some(Target::Ring)
+                } else if position[0].hypot(position[1]) < RING_INNER {
+                    Some(Target::Shape)
+                } else {
+                    return (event::Status::Ignored, None);
+                };
+                position
+            }
+            // Keeps picking outside of the wheel until the button is released
+            shader::Event::Mouse(mouse::Event::CursorMoved { position })
+                if state.target.is_some() =>
+            {
+                let position = Point::new(position.x - bounds.x, position.y - bounds.y);
+                picker_position(position, bounds)
+            }
+            shader::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                if state.target.is_some() =>
+            {
+                state.target = None;
+                return (event::Status::Captured, None);
+            }
+            _ => return (event::Status::Ignored, None),
+        };
+
+        let hue = self.hue(state);
+        let hsv = match state.target {
+            Some(Target::Ring) => [wheel::hue(position), self.saturation, self.value],
+            _ => {
+                let [saturation, value] = self.shape.saturation_value(hue, position);
+                [hue, saturation, value]
+            }
+        };
+
+        state.hue = Some(hsv[0]);
+        (event::Status::Captured, Some((self.on_change)(hsv)))
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if state.target.is_some() || cursor.is_over(bounds) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        _cursor: mouse::Cursor,
+        _bounds: Rectangle,
+    ) -> Self::Primitive {
+        Primitive::new(
+            self.hue(state),
+            self.saturation,
+            self.value,
+            self.shape,
+            self.cvd,
+        )
+    }
+}
+
+/// Converts a position relative to `bounds` into the centered coordinates
+/// of [`crate::wheel`]
+pub fn picker_position(position: Point, bounds: Rectangle) -> [f32; 2] {
+    [
+        position.x / bounds.width * 2.0 - 1.0,
+        1.0 - position.y / bounds.height * 2.0,
+    ]
+}
+
+#[derive(Debug)]
+pub struct Primitive {
+    uniforms: Uniforms,
+    cvd: Cvd,
+}
+
+impl Primitive {
+    pub fn new(
+        hue: f32,
+        saturation: f32,
+        value: f32,
+        shape: WheelShape,
+        cvd: Option<Simulation>,
+    ) -> Self {
+        Self {
+            uniforms: Uniforms {
+                hue,
+                shape: match shape {
+                    WheelShape::Triangle => 0,
+                    WheelShape::Square => 1,
+                },
+                ring_marker: wheel::ring_position(hue),
+                shape_marker: shape.position(hue, saturation, value),
+                _padding: [0.0; 2],
+            },
+            cvd: Cvd::new(cvd),
+        }
+    }
+}
+
+impl shader::Primitive for Primitive {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        storage: &mut Storage,
+        bounds: &Rectangle,
+        viewport: &Viewport,
+    ) {
+        if !storage.has::<ShaderPipeline<Uniforms, 0>>() {
+            storage.store(ShaderPipeline::<Uniforms, 0>::new(
+                device,
+                queue,
+                format,
+                graph_shader!("wheel.wgsl"),
+            ));
+        }
+
+        let pipeline = storage.get_mut::<ShaderPipeline<Uniforms, 0>>().unwrap();
+        pipeline.write(queue, &self.uniforms, &self.cvd);
+    }
+
+    fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        storage: &Storage,
+        target: &wgpu::TextureView,
+        clip_bounds: &Rectangle<u32>,
+    ) {
+        let pipeline = storage.get::<ShaderPipeline<Uniforms, 0>>().unwrap();
+        pipeline.render(target, encoder, clip_bounds);
+    }
+}
+
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Uniforms {
+    hue: f32,
+    shape: u32,
+    ring_marker: [f32; 2],
+    shape_marker: [f32; 2],
+    _padding: [f32; 2],
+}
@@ -0,0 +1,112 @@
+struct Wheel {
+    hue: f32,
+    shape: u32,
+    ring_marker: vec2<f32>,
+    shape_marker: vec2<f32>,
+}
+
+const SHAPE_TRIANGLE = 0u;
+const SHAPE_SQUARE = 1u;
+
+// See `crate::wheel`
+const RING_INNER = 0.8;
+const SHAPE_RADIUS = 0.75;
+
+@group(0) @binding(0) var<uniform> wheel: Wheel;
+
+@fragment
+fn fs_main(
+    @builtin(position) _clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+) -> @location(0) vec4<f32> {
+    // Centered, from -1 to 1 with y going up
+    let position = uv * 2.0 - 1.0;
+    let pixel = fwidth(position.x);
+    let distance = length(position);
+
+    var color = vec4<f32>(0.0);
+
+    // The hue ring, with smoothed edges
+    let ring = smoothstep(RING_INNER - pixel, RING_INNER, distance)
+        * (1.0 - smoothstep(1.0 - pixel, 1.0, distance));
+    if ring > 0.0 {
+        let hue = (degrees(atan2(position.y, position.x)) + 360.0) % 360.0;
+        color = vec4<f32>(hsv_to_rgb(hue, 1.0, 1.0), ring);
+    }
+
+    switch wheel.shape {
+        case SHAPE_TRIANGLE: {
+            let pure = corner(0.0);
+            let white = corner(120.0);
+            let black = corner(240.0);
+
+            // The value and chroma axes of the triangle
+            let value_axis = white - black;
+            let chroma_axis = pure - white;
+            let relative = position - black;
+            let det = value_axis.x * chroma_axis.y - value_axis.y * chroma_axis.x;
+            let value = (relative.x * chroma_axis.y - relative.y * chroma_axis.x) / det;
+            let chroma = (value_axis.x * relative.y - value_axis.y * relative.x) / det;
+
+            if value >= 0.0 && value <= 1.0 && chroma >= 0.0 && chroma <= value {
+                let saturation = select(0.0, chroma / value, value > 0.0);
+                color = vec4<f32>(hsv_to_rgb(wheel.hue, saturation, value), 1.0);
+            }
+        }
+        case SHAPE_SQUARE: {
+            let half_side = SHAPE_RADIUS * 0.70710678;
+            if abs(position.x) <= half_side && abs(position.y) <= half_side {
+                let sv = (position / half_side + 1.0) / 2.0;
+                color = vec4<f32>(hsv_to_rgb(wheel.hue, sv.x, sv.y), 1.0);
+            }
+        }
+        default: {}
+    }
+
+    color = vec4<f32>(simulate_srgb(color.xyz), color.w);
+
+    color = circle_marker(color, position, wheel.ring_marker, pixel);
+    return circle_marker(color, position, wheel.shape_marker, pixel);
+}
+
+/// A corner of the triangle, `offset` degrees from the hue
+fn corner(offset: f32) -> vec2<f32> {
+    let angle = radians(wheel.hue + offset);
+    return vec2<f32>(cos(angle), sin(angle)) * SHAPE_RADIUS;
+}
+
+/// A white ring with dark outlines around `marker`
+fn circle_marker(color: vec4<f32>, position: vec2<f32>, marker: vec2<f32>, pixel: f32) -> vec4<f32> {
+    let distance = length(position - marker) / pixel;
+    if distance > 4.0 && distance < 8.0 {
+        if distance > 5.0 && distance < 7.0 {
+            return vec4<f32>(1.0);
+        }
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    return color;
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> vec3<f32> {
+    let c = value * saturation;
+    let h_ = hue / 60.0;
+    let x = c * (1.0 - abs(h_ % 2.0 - 1.0));
+
+    var rgb = vec3<f32>(0.0);
+    if 0.0 <= h_ && h_ < 1.0 {
+        rgb = vec3<f32>(c, x, 0.0);
+    } else if 1.0 <= h_ && h_ < 2.0 {
+        rgb = vec3<f32>(x, c, 0.0);
+    } else if 2.0 <= h_ && h_ < 3.0 {
+        rgb = vec3<f32>(0.0, c, x);
+    } else if 3.0 <= h_ && h_ < 4.0 {
+        rgb = vec3<f32>(0.0, x, c);
+    } else if 4.0 <= h_ && h_ < 5.0 {
+        rgb = vec3<f32>(x, 0.0, c);
+    } else {
+        rgb = vec3<f32>(c, 0.0, x);
+    }
+
+    return rgb + (value - c);
+}
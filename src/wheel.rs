@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Geometry of the hue wheel and the OKLCh disc pickers, shared by their
+//! shaders and their mouse handling.
+//!
+//! Positions are relative to the center of the picker, from -1 to 1 with
+//! y going up. Hues are degrees counterclockwise from the right.
+
+use std::f32::consts::FRAC_1_SQRT_2;
+
+/// The inner radius of the hue ring, the outer radius is 1
+pub const RING_INNER: f32 = 0.8;
+/// The radius the inner triangle or square is inscribed in, leaving a gap
+/// to the ring
+pub const SHAPE_RADIUS: f32 = 0.75;
+
+/// The saturation and value picker inside of the hue ring
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WheelShape {
+    /// A triangle pointing at its hue on the ring, rotating with it
+    #[default]
+    Triangle,
+    /// Saturation to the right, value to the top
+    Square,
+}
+
+/// Whether `position` is on the hue ring
+pub fn in_ring(position: [f32; 2]) -> bool {
+    let distance = position[0].hypot(position[1]);
+    (RING_INNER..=1.0).contains(&distance)
+}
+
+/// The hue at the angle of `position`
+pub fn hue(position: [f32; 2]) -> f32 {
+    position[1]
+        .atan2(position[0])
+        .to_degrees()
+        .rem_euclid(360.0)
+}
+
+/// The position on the middle of the ring of `hue`
+pub fn ring_position(hue: f32) -> [f32; 2] {
+    let radius = (RING_INNER + 1.0) / 2.0;
+    let (sin, cos) = hue.to_radians().sin_cos();
+    [radius * cos, radius * sin]
+}
+
+/// The corners of the triangle of `hue`: the pure hue, white and black
+fn triangle(hue: f32) -> [[f32; 2]; 3] {
+    [0.0, 120.0, 240.0].map(|offset: f32| {
+        let (sin, cos) = (hue + offset).to_radians().sin_cos();
+        [SHAPE_RADIUS * cos, SHAPE_RADIUS * sin]
+    })
+}
+
+/// Half the side of the square
+const HALF_SIDE: f32 = SHAPE_RADIUS * FRAC_1_SQRT_2;
+
+impl WheelShape {
+    /// The position of a saturation and value in the shape of `hue`
+    pub fn position(self, hue: f32, saturation: f32, value: f32) -> [f32; 2] {
+        match self {
+            WheelShape::Triangle => {
+                // Black, toward white by value, toward the hue by saturation
+                let [pure, white, black] = triangle(hue);
+                [0, 1].map(|i| {
+                    black[i]
+                        + value * (white[i] - black[i])
+                        + value * saturation * (pure[i] - white[i])
+                })
+            }
+            WheelShape::Square => [
+                HALF_SIDE * (saturation * 2.0 - 1.0),
+                HALF_SIDE * (value * 2.0 - 1.0),
+            ],
+        }
+    }
+
+    /// The saturation and value at `position` in the shape of `hue`,
+    /// clamped into the shape
+    pub fn saturation_value(self, hue: f32, position: [f32; 2]) -> [f32; 2] {
+        match self {
+            WheelShape::Triangle => {
+                let [pure, white, black] = triangle(hue);
+                let value_axis = [white[0] - black[0], white[1] - black[1]];
+                let saturation_axis = [pure[0] - white[0], pure[1] - white[1]];
+                let relative = [position[0] - black[0], position[1] - black[1]];
+
+                let det = value_axis[0] * saturation_axis[1] - value_axis[1] * saturation_axis[0];
+                let value =
+                    (relative[0] * saturation_axis[1] - relative[1] * saturation_axis[0]) / det;
+                let chroma = (value_axis[0] * relative[1] - value_axis[1] * relative[0]) / det;
+
+                let value = value.clamp(0.0, 1.0);
+                let chroma = chroma.clamp(0.0, value);
+                let saturation = if value > 0.0 { chroma / value } else { 0.0 };
+                [saturation, value]
+            }
+            WheelShape::Square => position.map(|c| ((c / HALF_SIDE + 1.0) / 2.0).clamp(0.0, 1.0)),
+        }
+    }
+}
+
+/// The chroma and hue at `position` on a disc with `max_chroma` at its edge
+pub fn disc_chroma_hue(position: [f32; 2], max_chroma: f32) -> [f32; 2] {
+    let distance = position[0].hypot(position[1]).min(1.0);
+    [distance * max_chroma, hue(position)]
+}
+
+/// The position of a chroma and hue on a disc with `max_chroma` at its edge
+pub fn disc_position(chroma: f32, hue: f32, max_chroma: f32) -> [f32; 2] {
+    let distance = (chroma / max_chroma).clamp(0.0, 1.0);
+    let (sin, cos) = hue.to_radians().sin_cos();
+    [distance * cos, distance * sin]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        disc_chroma_hue, disc_position, hue, in_ring, ring_position, WheelShape, RING_INNER,
+    };
+
+    #[test]
+    fn ring() {
+        assert!(aprox_eq(hue([1.0, 0.0]), 0.0));
+        assert!(aprox_eq(hue([0.0, 1.0]), 90.0));
+        assert!(aprox_eq(hue([0.0, -1.0]), 270.0));
+        assert!(in_ring(ring_position(200.0)));
+        assert!(aprox_eq(hue(ring_position(200.0)), 200.0));
+        assert!(!in_ring([RING_INNER / 2.0, 0.0]));
+    }
+
+    #[test]
+    fn shapes_round_trip() {
+        for shape in [WheelShape::Triangle, WheelShape::Square] {
+            for [saturation, value] in [[0.2, 0.7], [1.0, 1.0], [0.0, 0.5], [0.6, 0.1]] {
+                let position = shape.position(130.0, saturation, value);
+                let [s, v] = shape.saturation_value(130.0, position);
+                assert!(aprox_eq(s, saturation), "{shape:?} {saturation} {s}");
+                assert!(aprox_eq(v, value), "{shape:?} {value} {v}");
+            }
+        }
+    }
+
+    #[test]
+    fn triangle_corners() {
+        // The pure hue points at its hue on the ring
+        let pure = WheelShape::Triangle.position(60.0, 1.0, 1.0);
+        assert!(aprox_eq(hue(pure), 60.0));
+
+        // Outside of the triangle is clamped onto it
+        let [s, v] = WheelShape::Triangle.saturation_value(60.0, [-5.0, -5.0]);
+        assert!((0.0..=1.0).contains(&s) && (0.0..=1.0).contains(&v));
+        assert_eq!(
+            WheelShape::Square.saturation_value(0.0, [5.0, -5.0]),
+            [1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn disc() {
+        let [chroma, h] = disc_chroma_hue(disc_position(0.1, 300.0, 0.37), 0.37);
+        assert!(aprox_eq(chroma, 0.1) && aprox_eq(h, 300.0));
+        assert!(aprox_eq(disc_chroma_hue([3.0, 0.0], 0.37)[0], 0.37));
+    }
+
+    fn aprox_eq(a: f32, b: f32) -> bool {
+        const EPSILON: f32 = 1e-3;
+
+        a > b - EPSILON && a < b + EPSILON
+    }
+}
@@ -4,18 +4,34 @@ use cosmic::{
     iced::{
         border,
         gradient::{ColorStop, Linear},
+        keyboard::{self, key::Named, Key},
         mouse, touch, Background, Border, Color, Element, Event, Gradient, Length, Padding, Point,
         Radians, Rectangle, Shadow, Size,
     },
-    iced_core::{layout, renderer, widget::tree},
-    theme,
+    iced_core::{
+        layout,
+        renderer::{self, Renderer as _},
+        widget::{
+            operation::{self, Operation},
+            tree, Id,
+        },
+    },
+    iced_renderer::fallback,
     widget::{
-        self,
         slider::{self, HandleShape, RailBackground},
         Widget,
     },
 };
 
+use crate::{
+    cvd::Simulation,
+    shaders::{self, disc::ChromaDisc, wheel::ColorWheel, Picking},
+    wheel::WheelShape,
+};
+
+/// The width and height of the hue wheel and the chroma disc
+const PICKER_SIZE: f32 = 240.0;
+
 pub struct ColorBlock {
     color: Color,
     border: [bool; 4],
@@ -49,38 +65,63 @@ impl ColorBlock {
     }
 }
 
-impl<'a, Message: 'a> From<ColorBlock> for cosmic::Element<'a, Message> {
-    fn from(value: ColorBlock) -> Self {
-        let ColorBlock {
-            color,
-            border,
-            width,
-            height,
-        } = value;
+impl<Message> Widget<Message, cosmic::Theme, cosmic::Renderer> for ColorBlock {
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut tree::Tree,
+        _renderer: &cosmic::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::atomic(limits, self.width, self.height)
+    }
 
-        widget::container(widget::Space::with_height(height))
-            .class(theme::Container::custom(move |theme| {
-                let cosmic = theme.cosmic();
-                let radius = cosmic.corner_radii.radius_xs;
+    fn draw(
+        &self,
+        _tree: &tree::Tree,
+        renderer: &mut cosmic::Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: layout::Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let radius = theme.cosmic().corner_radii.radius_xs;
+        let radius = [
+            radius[0] * (self.border[0] as i8) as f32,
+            radius[1] * (self.border[1] as i8) as f32,
+            radius[2] * (self.border[2] as i8) as f32,
+            radius[3] * (self.border[3] as i8) as f32,
+        ];
 
-                cosmic::widget::container::Style {
-                    background: Some(value.color.into()),
-                    border: Border {
-                        radius: [
-                            radius[0] * (border[0] as i8) as f32,
-                            radius[1] * (border[1] as i8) as f32,
-                            radius[2] * (border[2] as i8) as f32,
-                            radius[3] * (border[3] as i8) as f32,
-                        ]
-                        .into(),
-                        color,
-                        ..Default::default()
-                    },
+        if self.color.a < 1.0 {
+            draw_checkerboard(renderer, bounds, radius);
+        }
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: Border {
+                    radius: radius.into(),
                     ..Default::default()
-                }
-            }))
-            .width(width)
-            .into()
+                },
+                shadow: Shadow::default(),
+            },
+            self.color,
+        );
+    }
+}
+
+impl<'a, Message: 'a> From<ColorBlock> for cosmic::Element<'a, Message> {
+    fn from(value: ColorBlock) -> Self {
+        Self::new(value)
     }
 }
 
@@ -88,12 +129,15 @@ pub fn color_block(color: Color) -> ColorBlock {
     ColorBlock::new(color)
 }
 
+/// A slider over a gradient of `color_stops`. `id` must stay the same
+/// across views for keyboard focus and screen readers to follow it.
 pub fn color_slider<'a, Message>(
+    id: Id,
     range: RangeInclusive<f32>,
     value: f32,
     on_change: impl Fn(f32) -> Message + 'a,
-    color_stops: &'static [ColorStop],
-) -> cosmic::Element<'a, Message>
+    color_stops: &[ColorStop],
+) -> ColorSlider<'a, Message>
 where
     Message: Clone + 'a,
 {
@@ -101,26 +145,251 @@ where
         value,
         range,
         background: Gradient::Linear(Linear::new(FRAC_PI_2).add_stops(color_stops.iter().cloned())),
-        scroll_steps: 0.01,
+        checkerboard: false,
+        id,
+        snap: None,
+        name: None,
+        on_change: Box::new(on_change),
+    }
+}
+
+/// A slider fading from transparent to `color`, drawn over a checkerboard.
+/// `id` must stay the same across views like [`color_slider`].
+pub fn alpha_slider<'a, Message>(
+    id: Id,
+    range: RangeInclusive<f32>,
+    value: f32,
+    on_change: impl Fn(f32) -> Message + 'a,
+    color: Color,
+) -> ColorSlider<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    ColorSlider {
+        value,
+        range,
+        background: Gradient::Linear(
+            Linear::new(FRAC_PI_2)
+                .add_stop(0.0, Color { a: 0.0, ..color })
+                .add_stop(1.0, Color { a: 1.0, ..color }),
+        ),
+        checkerboard: true,
+        id,
+        snap: None,
+        name: None,
         on_change: Box::new(on_change),
     }
+}
+
+/// A hue ring around a saturation and value `shape`, picking HSV
+pub fn hue_wheel<'a, Message: 'a>(
+    hsv: [f32; 3],
+    shape: WheelShape,
+    cvd: Option<Simulation>,
+    on_change: impl Fn([f32; 3]) -> Message + 'a,
+) -> cosmic::Element<'a, Message> {
+    cosmic::iced_widget::shader(ColorWheel {
+        hue: hsv[0],
+        saturation: hsv[1],
+        value: hsv[2],
+        shape,
+        cvd,
+        on_change: Box::new(on_change),
+    })
+    .width(PICKER_SIZE)
+    .height(PICKER_SIZE)
     .into()
 }
 
+/// An OKLCh chroma and hue disc at the lightness of `lch`, with
+/// `max_chroma` at its edge
+pub fn chroma_disc<'a, Message: 'a>(
+    lch: [f32; 3],
+    max_chroma: f32,
+    cvd: Option<Simulation>,
+    on_change: impl Fn([f32; 2]) -> Message + 'a,
+) -> cosmic::Element<'a, Message> {
+    cosmic::iced_widget::shader(ChromaDisc {
+        lightness: lch[0],
+        chroma: lch[1],
+        hue: lch[2],
+        max_chroma,
+        cvd,
+        on_change: Box::new(on_change),
+    })
+    .width(PICKER_SIZE)
+    .height(PICKER_SIZE)
+    .into()
+}
+
+/// Draws a light/dark checkerboard inside `bounds`, leaving the rounded
+/// corners to the light base so the cells never poke out of them.
+fn draw_checkerboard<Renderer>(renderer: &mut Renderer, bounds: Rectangle, radius: [f32; 4])
+where
+    Renderer: renderer::Renderer,
+{
+    const LIGHT: Color = Color::from_rgb(0.8, 0.8, 0.8);
+    const DARK: Color = Color::from_rgb(0.5, 0.5, 0.5);
+
+    let cell = (bounds.height / 2.0).min(8.0);
+    if cell <= 0.0 {
+        return;
+    }
+
+    renderer.fill_quad(
+        renderer::Quad {
+            bounds,
+            border: Border {
+                radius: radius.into(),
+                ..Default::default()
+            },
+            shadow: Shadow::default(),
+        },
+        LIGHT,
+    );
+
+    let [top_left, top_right, bottom_right, bottom_left] =
+        radius.map(|r| r.min(bounds.width / 2.0).min(bounds.height / 2.0));
+    let in_corner = |cell: &Rectangle| {
+        let left = cell.x - bounds.x;
+        let right = bounds.x + bounds.width - (cell.x + cell.width);
+        let top = cell.y - bounds.y;
+        let bottom = bounds.y + bounds.height - (cell.y + cell.height);
+
+        (left < top_left && top < top_left)
+            || (right < top_right && top < top_right)
+            || (right < bottom_right && bottom < bottom_right)
+            || (left < bottom_left && bottom < bottom_left)
+    };
+
+    let columns = (bounds.width / cell).ceil() as usize;
+    let rows = (bounds.height / cell).ceil() as usize;
+    for row in 0..rows {
+        for column in 0..columns {
+            if (row + column) % 2 == 0 {
+                continue;
+            }
+
+            let x = bounds.x + column as f32 * cell;
+            let y = bounds.y + row as f32 * cell;
+            let cell_bounds = Rectangle {
+                x,
+                y,
+                width: cell.min(bounds.x + bounds.width - x),
+                height: cell.min(bounds.y + bounds.height - y),
+            };
+            if in_corner(&cell_bounds) {
+                continue;
+            }
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: cell_bounds,
+                    border: Border::default(),
+                    shadow: Shadow::default(),
+                },
+                DARK,
+            );
+        }
+    }
+}
+
+/// Steps in a slider's range, the step of the arrow keys and scroll wheel
+const SLIDER_STEPS: f32 = 100.0;
+/// How much finer and coarser the steps get with Shift and Ctrl, also the
+/// slowdown of precise drags and the page step
+const STEP_SCALE: f32 = 10.0;
+/// The pixels of a scrolled line, for touchpads scrolling by pixels
+const SCROLL_LINE: f32 = 20.0;
+
 pub struct ColorSlider<'a, Message> {
     value: f32,
     range: RangeInclusive<f32>,
     background: Gradient,
-    scroll_steps: f32,
+    /// The value is rounded to a multiple of this, e.g. whole degrees
+    snap: Option<f32>,
+    checkerboard: bool,
+    id: Id,
+    /// Read by screen readers, usually the component label
+    name: Option<String>,
     on_change: Box<dyn Fn(f32) -> Message + 'a>,
 }
 
-impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer> for ColorSlider<'a, Message>
+impl<'a, Message> ColorSlider<'a, Message> {
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn snap(mut self, snap: Option<f32>) -> Self {
+        self.snap = snap;
+        self
+    }
+
+    /// The step of the arrow keys and the scroll wheel, derived from the
+    /// range. Shift makes it finer and Ctrl coarser, but never finer than
+    /// the snap.
+    fn step(&self, modifiers: keyboard::Modifiers) -> f32 {
+        let mut step = (self.range.end() - self.range.start()) / SLIDER_STEPS;
+        if modifiers.shift() {
+            step /= STEP_SCALE;
+        } else if modifiers.control() {
+            step *= STEP_SCALE;
+        }
+
+        self.snap.map_or(step, |snap| step.max(snap))
+    }
+
+    /// Moves the value to the cursor, or by a fraction of the cursor's
+    /// movement while Shift is held for a precise drag. Ctrl rounds to
+    /// coarse steps.
+    fn drag(
+        &mut self,
+        state: &mut ColorSliderState,
+        bounds: Rectangle,
+        position: Point,
+        shell: &mut cosmic::iced_core::Shell<'_, Message>,
+    ) {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let width = bounds.width - bounds.height;
+
+        let value = if state.modifiers.shift() {
+            let moved = (position.x - state.last_x) / width;
+            state.drag_value + moved * (end - start) / STEP_SCALE
+        } else {
+            let percent = ((position.x - bounds.x) / width).clamp(0.0, 1.0);
+            let value = start + (end - start) * percent;
+            if state.modifiers.control() {
+                let step = self.step(state.modifiers);
+                start + ((value - start) / step).round() * step
+            } else {
+                value
+            }
+        };
+
+        state.last_x = position.x;
+        state.drag_value = value.clamp(start, end);
+        self.set_value(value, shell);
+    }
+
+    /// Snaps and clamps `value`, then publishes it
+    fn set_value(&mut self, value: f32, shell: &mut cosmic::iced_core::Shell<'_, Message>) {
+        let value = match self.snap {
+            Some(snap) => (value / snap).round() * snap,
+            None => value,
+        };
+
+        self.value = value.clamp(*self.range.start(), *self.range.end());
+        shell.publish((self.on_change)(self.value));
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, cosmic::Theme, Renderer> for ColorSlider<'a, Message>
 where
     Renderer: renderer::Renderer,
 {
     fn state(&self) -> cosmic::iced_core::widget::tree::State {
-        tree::State::new(ColorSliderState { is_dragging: false })
+        tree::State::new(ColorSliderState::default())
     }
 
     fn size(&self) -> cosmic::iced::Size<Length> {
@@ -143,7 +412,7 @@ where
         &self,
         tree: &cosmic::iced_core::widget::Tree,
         renderer: &mut Renderer,
-        theme: &Theme,
+        theme: &cosmic::Theme,
         style: &renderer::Style,
         layout: cosmic::iced_core::Layout<'_>,
         cursor: cosmic::iced_core::mouse::Cursor,
@@ -160,6 +429,11 @@ where
             height: bounds.height,
         };
 
+        if self.checkerboard {
+            let radius = (rail_bounds.height / 2.0).min(10.0);
+            draw_checkerboard(renderer, rail_bounds, [radius; 4]);
+        }
+
         renderer.fill_quad(
             renderer::Quad {
                 bounds: rail_bounds,
@@ -183,6 +457,32 @@ where
             },
             Background::Color(Color::TRANSPARENT),
         );
+
+        if tree.state.downcast_ref::<ColorSliderState>().is_focused {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: bounds.expand(2.0),
+                    border: Border {
+                        color: theme.cosmic().accent_color().into(),
+                        width: 2.0,
+                        radius: 12f32.into(),
+                    },
+                    shadow: Shadow::default(),
+                },
+                Background::Color(Color::TRANSPARENT),
+            );
+        }
+    }
+
+    fn operate(
+        &self,
+        tree: &mut cosmic::iced_core::widget::Tree,
+        _layout: layout::Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation<()>,
+    ) {
+        let state = tree.state.downcast_mut::<ColorSliderState>();
+        operation.focusable(state, Some(&self.id));
     }
 
     fn on_event(
@@ -197,24 +497,28 @@ where
         _viewport: &Rectangle,
     ) -> cosmic::iced_core::event::Status {
         let state = tree.state.downcast_mut::<ColorSliderState>();
-
-        let mut change = |position: &Point| {
-            let bounds = layout.bounds();
-            let relative_x = (position.x - bounds.x) / (bounds.width - bounds.height);
-            let percent = relative_x.clamp(0.0, 1.0);
-
-            self.value = self.range.start() * (1.0 - percent) + self.range.end() * percent;
-            shell.publish((self.on_change)(self.value));
-        };
+        let bounds = layout.bounds();
 
         match &event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = *modifiers;
+            }
             Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
             | Event::Touch(touch::Event::FingerPressed { .. }) => {
-                if let Some(position) = cursor.position_over(layout.bounds()) {
+                if let Some(position) = cursor.position_over(bounds) {
                     state.is_dragging = true;
-                    change(&position);
+                    state.is_focused = true;
+                    // A precise drag starts from the current value
+                    if state.modifiers.shift() {
+                        state.last_x = position.x;
+                        state.drag_value = self.value;
+                    } else {
+                        self.drag(state, bounds, position, shell);
+                    }
                     return cosmic::iced_core::event::Status::Captured;
                 }
+
+                state.is_focused = false;
             }
             Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
             | Event::Touch(touch::Event::FingerLifted { .. })
@@ -227,8 +531,8 @@ where
             Event::Mouse(mouse::Event::CursorMoved { .. })
             | Event::Touch(touch::Event::FingerMoved { .. }) => {
                 if state.is_dragging {
-                    if let Some(position) = cursor.position().as_ref() {
-                        change(position);
+                    if let Some(position) = cursor.position() {
+                        self.drag(state, bounds, position, shell);
                     }
 
                     return cosmic::iced_core::event::Status::Captured;
@@ -236,22 +540,38 @@ where
             }
 
             Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
-                if !state.is_dragging && cursor.is_over(layout.bounds()) {
-                    match delta {
-                        mouse::ScrollDelta::Lines { x, y } => {
-                            self.value += *y * self.scroll_steps;
-                            self.value = self.value.clamp(*self.range.start(), *self.range.end());
-                            shell.publish((self.on_change)(self.value));
-                        }
-                        mouse::ScrollDelta::Pixels { x, y } => {
-                            self.value += *y * self.scroll_steps;
-                            self.value = self.value.clamp(*self.range.start(), *self.range.end());
-                            shell.publish((self.on_change)(self.value));
-                        }
-                    }
+                if !state.is_dragging && cursor.is_over(bounds) {
+                    let (x, y) = match *delta {
+                        mouse::ScrollDelta::Lines { x, y } => (x, y),
+                        mouse::ScrollDelta::Pixels { x, y } => (x / SCROLL_LINE, y / SCROLL_LINE),
+                    };
+                    // Shift turns vertical scrolling horizontal on some systems
+                    let lines = if y.abs() >= x.abs() { y } else { x };
+
+                    let value = self.value + lines * self.step(state.modifiers);
+                    self.set_value(value, shell);
                     return cosmic::iced_core::event::Status::Captured;
                 }
             }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Named(key),
+                modifiers,
+                ..
+            }) if state.is_focused => {
+                let step = self.step(*modifiers);
+                let value = match key {
+                    Named::ArrowLeft | Named::ArrowDown => self.value - step,
+                    Named::ArrowRight | Named::ArrowUp => self.value + step,
+                    Named::PageDown => self.value - step * STEP_SCALE,
+                    Named::PageUp => self.value + step * STEP_SCALE,
+                    Named::Home => *self.range.start(),
+                    Named::End => *self.range.end(),
+                    _ => return cosmic::iced_core::event::Status::Ignored,
+                };
+
+                self.set_value(value, shell);
+                return cosmic::iced_core::event::Status::Captured;
+            }
             _ => (),
         }
 
@@ -276,10 +596,58 @@ where
             mouse::Interaction::default()
         }
     }
+
+    fn id(&self) -> Option<Id> {
+        Some(self.id.clone())
+    }
+
+    fn set_id(&mut self, id: Id) {
+        self.id = id;
+    }
+
+    #[cfg(feature = "a11y")]
+    fn a11y_nodes(
+        &self,
+        layout: layout::Layout<'_>,
+        _tree: &tree::Tree,
+        cursor: mouse::Cursor,
+    ) -> cosmic::iced_accessibility::A11yTree {
+        use cosmic::iced_accessibility::{
+            accesskit::{NodeBuilder, Rect, Role},
+            A11yTree,
+        };
+
+        let Rectangle {
+            x,
+            y,
+            width,
+            height,
+        } = layout.bounds();
+
+        let mut node = NodeBuilder::new(Role::Slider);
+        node.set_bounds(Rect::new(
+            x as f64,
+            y as f64,
+            (x + width) as f64,
+            (y + height) as f64,
+        ));
+        if let Some(name) = &self.name {
+            node.set_name(name.clone());
+        }
+        if cursor.is_over(layout.bounds()) {
+            node.set_hovered();
+        }
+        node.set_min_numeric_value(*self.range.start() as f64);
+        node.set_max_numeric_value(*self.range.end() as f64);
+        node.set_numeric_value(self.value as f64);
+        node.set_numeric_value_step(self.step(keyboard::Modifiers::empty()) as f64);
+
+        A11yTree::leaf(node, self.id.clone())
+    }
 }
 
-impl<'a, Message, Theme, Renderer> From<ColorSlider<'a, Message>>
-    for Element<'a, Message, Theme, Renderer>
+impl<'a, Message, Renderer> From<ColorSlider<'a, Message>>
+    for Element<'a, Message, cosmic::Theme, Renderer>
 where
     Renderer: renderer::Renderer,
     Message: Clone + 'a,
@@ -289,7 +657,204 @@ where
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 struct ColorSliderState {
     is_dragging: bool,
+    is_focused: bool,
+    modifiers: keyboard::Modifiers,
+    /// The cursor x and unsnapped value of the last drag event, precise
+    /// drags move relative to them
+    last_x: f32,
+    drag_value: f32,
+}
+
+impl operation::Focusable for ColorSliderState {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    fn focus(&mut self) {
+        self.is_focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.is_focused = false;
+    }
+}
+
+/// Picks a position on `content` by pressing or dragging on it like the
+/// graph shaders do, for the graphs drawn without them
+pub fn pick_area<'a, Message: 'a>(
+    content: impl Into<cosmic::Element<'a, Message>>,
+    on_pick: impl Fn([f32; 2]) -> Message + 'a,
+) -> PickArea<'a, Message> {
+    PickArea {
+        content: content.into(),
+        on_pick: Box::new(on_pick),
+    }
+}
+
+pub struct PickArea<'a, Message> {
+    content: cosmic::Element<'a, Message>,
+    /// Called with the picked position, from 0 to 1 with y up
+    on_pick: Box<dyn Fn([f32; 2]) -> Message + 'a>,
+}
+
+impl<'a, Message> Widget<Message, cosmic::Theme, cosmic::Renderer> for PickArea<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<Picking>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(Picking::default())
+    }
+
+    fn children(&self) -> Vec<tree::Tree> {
+        vec![tree::Tree::new(&self.content)]
+    }
+
+    fn diff(&mut self, tree: &mut tree::Tree) {
+        tree.diff_children(std::slice::from_mut(&mut self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut tree::Tree,
+        renderer: &cosmic::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &tree::Tree,
+        renderer: &mut cosmic::Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut tree::Tree,
+        event: Event,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &cosmic::Renderer,
+        _clipboard: &mut dyn cosmic::iced_core::Clipboard,
+        shell: &mut cosmic::iced_core::Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> cosmic::iced_core::event::Status {
+        let Event::Mouse(event) = event else {
+            return cosmic::iced_core::event::Status::Ignored;
+        };
+
+        let state = tree.state.downcast_mut::<Picking>();
+        let (status, uv) = state.update_mouse(&event, layout.bounds(), cursor);
+        if let Some(uv) = uv {
+            shell.publish((self.on_pick)(uv));
+        }
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &tree::Tree,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &cosmic::Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<Picking>();
+        state.mouse_interaction(layout.bounds(), cursor)
+    }
+}
+
+impl<'a, Message: 'a> From<PickArea<'a, Message>> for cosmic::Element<'a, Message> {
+    fn from(value: PickArea<'a, Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Finds whether the window draws with wgpu from its renderer, publishing
+/// `on_change` when that differs from [`shaders::gpu_available`] so the view
+/// is rebuilt with graphs it can draw
+pub fn renderer_probe<Message>(on_change: Message) -> RendererProbe<Message> {
+    RendererProbe { on_change }
+}
+
+/// An empty widget, see [`renderer_probe`]
+pub struct RendererProbe<Message> {
+    on_change: Message,
+}
+
+impl<Message: Clone> Widget<Message, cosmic::Theme, cosmic::Renderer> for RendererProbe<Message> {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Shrink, Length::Shrink)
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut tree::Tree,
+        _renderer: &cosmic::Renderer,
+        _limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(Size::ZERO)
+    }
+
+    fn draw(
+        &self,
+        _tree: &tree::Tree,
+        _renderer: &mut cosmic::Renderer,
+        _theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        _layout: layout::Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+    }
+
+    fn on_event(
+        &mut self,
+        _tree: &mut tree::Tree,
+        _event: Event,
+        _layout: layout::Layout<'_>,
+        _cursor: mouse::Cursor,
+        renderer: &cosmic::Renderer,
+        _clipboard: &mut dyn cosmic::iced_core::Clipboard,
+        shell: &mut cosmic::iced_core::Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> cosmic::iced_core::event::Status {
+        // iced falls back to tiny-skia when wgpu can't draw to the window
+        let available = !matches!(renderer, fallback::Renderer::Secondary(_));
+        if shaders::set_gpu_available(available) {
+            shell.publish(self.on_change.clone());
+        }
+        cosmic::iced_core::event::Status::Ignored
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<RendererProbe<Message>> for cosmic::Element<'a, Message> {
+    fn from(value: RendererProbe<Message>) -> Self {
+        Self::new(value)
+    }
 }